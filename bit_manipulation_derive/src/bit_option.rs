@@ -0,0 +1,219 @@
+use proc_macro::{Delimiter, Group, Ident, Literal, Span, TokenStream, TokenTree};
+
+use crate::{split_top_level, Error};
+
+struct Variant {
+    name: Ident,
+    position: u8,
+}
+
+pub(crate) struct Enum {
+    name: Ident,
+    variants: Vec<Variant>,
+}
+
+pub(crate) fn parse_enum(input: TokenStream) -> Result<Enum, Error> {
+    let mut tokens = input.into_iter();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "enum" => break,
+            Some(TokenTree::Ident(ident))
+                if ident.to_string() == "struct" || ident.to_string() == "union" =>
+            {
+                return Err(Error::new(
+                    ident.span(),
+                    "`BitOption` can only be derived for enums",
+                ));
+            }
+            Some(_) => continue,
+            None => return Err(Error::new(Span::call_site(), "expected an enum")),
+        }
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "expected the name of the enum",
+            ))
+        }
+    };
+
+    let body = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(token) => {
+            return Err(Error::new(
+                token.span(),
+                "`BitOption` cannot be derived for generic enums",
+            ))
+        }
+        None => return Err(Error::new(name.span(), "expected the variants of the enum")),
+    };
+
+    let mut variants = Vec::new();
+    let mut next_position: u16 = 0;
+    for tokens in split_top_level(body.stream(), ',') {
+        let variant = parse_variant(tokens, next_position)?;
+        next_position = variant.position as u16 + 1;
+        variants.push(variant);
+    }
+
+    Ok(Enum { name, variants })
+}
+
+fn parse_variant(tokens: Vec<TokenTree>, next_position: u16) -> Result<Variant, Error> {
+    let mut tokens = tokens.into_iter();
+    let mut explicit_position = None;
+    let name = loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                if let Some(TokenTree::Group(attribute)) = tokens.next() {
+                    if let Some(position) = parse_bit_attribute(&attribute)? {
+                        explicit_position = Some(position);
+                    }
+                }
+            }
+            Some(TokenTree::Ident(name)) => break name,
+            Some(token) => return Err(Error::new(token.span(), "expected a variant")),
+            None => return Err(Error::new(Span::call_site(), "expected a variant")),
+        }
+    };
+
+    if let Some(TokenTree::Group(fields)) = tokens.next() {
+        return Err(Error::new(
+            fields.span(),
+            "`BitOption` can only be derived for enums without fields",
+        ));
+    }
+
+    let position = match explicit_position {
+        Some(position) => position,
+        None => u8::try_from(next_position).map_err(|_| {
+            Error::new(
+                name.span(),
+                "the position of this variant does not fit in a `u8`",
+            )
+        })?,
+    };
+
+    Ok(Variant { name, position })
+}
+
+fn parse_bit_attribute(attribute: &Group) -> Result<Option<u8>, Error> {
+    let mut tokens = attribute.stream().into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "bit" => {}
+        _ => return Ok(None),
+    }
+    let arguments = match tokens.next() {
+        Some(TokenTree::Group(arguments)) if arguments.delimiter() == Delimiter::Parenthesis => {
+            arguments
+        }
+        _ => {
+            return Err(Error::new(
+                attribute.span(),
+                "expected a position, as in `#[bit(3)]`",
+            ))
+        }
+    };
+    let mut arguments = arguments.stream().into_iter();
+    match (arguments.next(), arguments.next()) {
+        (Some(TokenTree::Literal(literal)), None) => parse_position(&literal).map(Some),
+        _ => Err(Error::new(
+            attribute.span(),
+            "expected a position, as in `#[bit(3)]`",
+        )),
+    }
+}
+
+fn parse_position(literal: &Literal) -> Result<u8, Error> {
+    let text = literal.to_string().replace('_', "");
+    let digits_end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (digits, suffix) = text.split_at(digits_end);
+    let integer_suffix = matches!(
+        suffix,
+        "" | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+    );
+    match digits.parse() {
+        Ok(position) if integer_suffix => Ok(position),
+        _ => Err(Error::new(
+            literal.span(),
+            "the position must be an integer lower than 256",
+        )),
+    }
+}
+
+pub(crate) fn expand(item: &Enum) -> TokenStream {
+    let name = &item.name;
+    let positions: Vec<String> = item
+        .variants
+        .iter()
+        .map(|variant| variant.position.to_string())
+        .collect();
+    let positions = positions.join(", ");
+    let variants: Vec<String> = item
+        .variants
+        .iter()
+        .map(|variant| format!("Self::{}", variant.name))
+        .collect();
+    let to_position: String = item
+        .variants
+        .iter()
+        .map(|variant| format!("Self::{} => {},", variant.name, variant.position))
+        .collect();
+    let from_position: String = item
+        .variants
+        .iter()
+        .map(|variant| {
+            format!(
+                "{} => ::core::option::Option::Some(Self::{}),",
+                variant.position, variant.name
+            )
+        })
+        .collect();
+
+    format!(
+        r#"
+        impl ::bit_manipulation::bit_manipulation::BitOption for {name} {{
+            type Repr = <::bit_manipulation::__private::Width<
+                {{ ::bit_manipulation::__private::narrowest_width(&[{positions}]) }},
+            > as ::bit_manipulation::__private::Backing>::Type;
+
+            const VARIANTS: &'static [Self] = &[{variants}];
+
+            fn position(self) -> u8 {{
+                match self {{ {to_position} }}
+            }}
+
+            fn from_position(position: u8) -> ::core::option::Option<Self> {{
+                match position {{
+                    {from_position}
+                    _ => ::core::option::Option::None,
+                }}
+            }}
+        }}
+
+        const _: () = ::core::assert!(
+            !::bit_manipulation::__private::has_duplicates(&[{positions}]),
+            "the bit positions of `{name}` must be unique",
+        );
+        "#,
+        variants = variants.join(", "),
+    )
+    .parse()
+    .unwrap()
+}
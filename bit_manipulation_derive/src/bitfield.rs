@@ -0,0 +1,300 @@
+use proc_macro::{Delimiter, Ident, Span, TokenStream, TokenTree};
+
+use crate::{split_top_level, Error};
+
+const BACKING_TYPES: [&str; 5] = ["u8", "u16", "u32", "u64", "u128"];
+
+struct Field {
+    attributes: TokenStream,
+    visibility: TokenStream,
+    name: Ident,
+    width: u8,
+    is_bool: bool,
+}
+
+pub(crate) struct Bitfield {
+    attributes: TokenStream,
+    visibility: TokenStream,
+    name: Ident,
+    backing: Ident,
+    fields: Vec<Field>,
+}
+
+pub(crate) fn parse_bitfield(arguments: TokenStream, item: TokenStream) -> Result<Bitfield, Error> {
+    let mut arguments = arguments.into_iter();
+    let backing = match (arguments.next(), arguments.next()) {
+        (Some(TokenTree::Ident(backing)), None)
+            if BACKING_TYPES.contains(&backing.to_string().as_str()) =>
+        {
+            backing
+        }
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "expected the backing integer, as in `#[bitfield(u32)]`",
+            ))
+        }
+    };
+
+    let mut tokens = item.into_iter();
+    let (attributes, visibility, keyword) = take_attributes_and_visibility(&mut tokens);
+    match keyword {
+        Some(TokenTree::Ident(keyword)) if keyword.to_string() == "struct" => {}
+        Some(token) => {
+            return Err(Error::new(
+                token.span(),
+                "`bitfield` can only be applied to structs",
+            ))
+        }
+        None => return Err(Error::new(Span::call_site(), "expected a struct")),
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "expected the name of the struct",
+            ))
+        }
+    };
+
+    let body = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(token) => {
+            return Err(Error::new(
+                token.span(),
+                "`bitfield` can only be applied to non-generic structs with named fields",
+            ))
+        }
+        None => return Err(Error::new(name.span(), "expected the fields of the struct")),
+    };
+
+    let fields = split_top_level(body.stream(), ',')
+        .into_iter()
+        .map(parse_field)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Bitfield {
+        attributes,
+        visibility,
+        name,
+        backing,
+        fields,
+    })
+}
+
+/// Consumes the attributes and the visibility in front of an item, returning them along with
+/// the first token that belongs to neither.
+fn take_attributes_and_visibility(
+    tokens: &mut impl Iterator<Item = TokenTree>,
+) -> (TokenStream, TokenStream, Option<TokenTree>) {
+    let mut attributes = Vec::new();
+    let mut visibility = Vec::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                attributes.push(TokenTree::Punct(punct));
+                attributes.extend(tokens.next());
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                visibility.push(TokenTree::Ident(ident));
+            }
+            Some(TokenTree::Group(group))
+                if group.delimiter() == Delimiter::Parenthesis && !visibility.is_empty() =>
+            {
+                visibility.push(TokenTree::Group(group));
+            }
+            token => {
+                return (
+                    attributes.into_iter().collect(),
+                    visibility.into_iter().collect(),
+                    token,
+                )
+            }
+        }
+    }
+}
+
+fn parse_field(tokens: Vec<TokenTree>) -> Result<Field, Error> {
+    let mut tokens = tokens.into_iter();
+    let (attributes, visibility, name) = match take_attributes_and_visibility(&mut tokens) {
+        (attributes, visibility, Some(TokenTree::Ident(name))) => (attributes, visibility, name),
+        (_, _, token) => {
+            return Err(Error::new(
+                token.map_or(Span::call_site(), |token| token.span()),
+                "expected a field name",
+            ))
+        }
+    };
+
+    match tokens.next() {
+        Some(TokenTree::Punct(colon)) if colon.as_char() == ':' => {}
+        _ => return Err(Error::new(name.span(), "expected a field type")),
+    }
+
+    let unsupported = "bitfield fields must be of type `bool` or `u1` to `u128`";
+    let ty = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(ty)), None) => ty,
+        (Some(token), _) => return Err(Error::new(token.span(), unsupported)),
+        (None, _) => return Err(Error::new(name.span(), "expected a field type")),
+    };
+    let ty_name = ty.to_string();
+    let (width, is_bool) = if ty_name == "bool" {
+        (1, true)
+    } else {
+        match ty_name.strip_prefix('u').and_then(|bits| bits.parse().ok()) {
+            Some(width @ 1..=128) => (width, false),
+            _ => return Err(Error::new(ty.span(), unsupported)),
+        }
+    };
+
+    Ok(Field {
+        attributes,
+        visibility,
+        name,
+        width,
+        is_bool,
+    })
+}
+
+/// Returns the narrowest unsigned integer able to hold `width` bits.
+fn narrowest_type(width: u8) -> &'static str {
+    match width {
+        0..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        33..=64 => "u64",
+        _ => "u128",
+    }
+}
+
+pub(crate) fn expand(item: &Bitfield) -> Result<TokenStream, Error> {
+    let Bitfield {
+        attributes,
+        visibility,
+        name,
+        backing,
+        fields,
+    } = item;
+    let bits = format!("::bit_manipulation::bit_manipulation::Bits<{backing}>");
+    let error = "::bit_manipulation::bit_manipulation::BitError";
+
+    let mut accessors = String::new();
+    let mut debug_fields = String::new();
+    let mut start: u32 = 0;
+    for field in fields {
+        let Field {
+            attributes,
+            visibility,
+            name: field,
+            width,
+            is_bool,
+        } = field;
+        let end = start + *width as u32;
+        if end > 128 {
+            return Err(Error::new(
+                field.span(),
+                format!("the fields of `{name}` do not fit in a `{backing}`"),
+            ));
+        }
+
+        if *is_bool {
+            accessors += &format!(
+                r#"
+                {attributes}
+                {visibility} fn {field}(&self) -> bool {{
+                    self.0.is_bit_on({start})
+                }}
+
+                /// Sets the `{field}` flag.
+                {visibility} fn set_{field}(&mut self, value: bool) {{
+                    if value {{
+                        self.0.set_bit({start});
+                    }} else {{
+                        self.0.clear_bit({start});
+                    }}
+                }}
+
+                /// Returns a copy of `self` with the `{field}` flag set to `value`.
+                {visibility} fn with_{field}(mut self, value: bool) -> Self {{
+                    self.set_{field}(value);
+                    self
+                }}
+                "#
+            );
+        } else {
+            let ty = narrowest_type(*width);
+            let (to_field, to_backing) = if ty == backing.to_string() {
+                (String::new(), String::new())
+            } else {
+                (format!(" as {ty}"), format!(" as {backing}"))
+            };
+            accessors += &format!(
+                r#"
+                {attributes}
+                {visibility} fn {field}(&self) -> {ty} {{
+                    self.0.get_field({start}..{end}).unwrap(){to_field}
+                }}
+
+                /// Sets the `{field}` field, which is {width} bits wide.
+                ///
+                /// Returns `BitError::FieldOverflow` and leaves the value unchanged if `value`
+                /// does not fit in the field.
+                {visibility} fn set_{field}(&mut self, value: {ty}) -> ::core::result::Result<(), {error}> {{
+                    self.0.set_field({start}..{end}, value{to_backing})
+                }}
+
+                /// Returns a copy of `self` with the `{field}` field set to `value`.
+                {visibility} fn with_{field}(mut self, value: {ty}) -> ::core::result::Result<Self, {error}> {{
+                    self.set_{field}(value)?;
+                    ::core::result::Result::Ok(self)
+                }}
+                "#
+            );
+        }
+        debug_fields += &format!(".field(\"{field}\", &self.{field}())");
+        start = end;
+    }
+
+    Ok(format!(
+        r#"
+        {attributes}
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        {visibility} struct {name}({bits});
+
+        const _: () = ::core::assert!(
+            {start} <= ::bit_manipulation::__private::bit_width::<{backing}>() as u32,
+            "the fields of `{name}` do not fit in a `{backing}`",
+        );
+
+        #[allow(dead_code)]
+        impl {name} {{
+            /// Creates a new instance with every field set to 0.
+            pub const fn new() -> Self {{
+                Self(::bit_manipulation::bit_manipulation::Bits::from_value(0))
+            }}
+
+            /// Creates a new instance from its packed bits.
+            pub const fn from_bits(bits: {bits}) -> Self {{
+                Self(bits)
+            }}
+
+            /// Returns the packed bits.
+            pub const fn into_bits(self) -> {bits} {{
+                self.0
+            }}
+
+            {accessors}
+        }}
+
+        impl ::core::fmt::Debug for {name} {{
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {{
+                f.debug_struct("{name}"){debug_fields}.finish()
+            }}
+        }}
+        "#
+    )
+    .parse()
+    .unwrap())
+}
@@ -0,0 +1,160 @@
+//! Derive macros for the `bit_manipulation` crate.
+//!
+//! This crate is not meant to be used directly: enable the `derive` feature of
+//! `bit_manipulation` and use the macros re-exported from there.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+mod bit_option;
+mod bitfield;
+
+pub(crate) struct Error {
+    span: Span,
+    message: String,
+}
+
+impl Error {
+    pub(crate) fn new(span: Span, message: impl Into<String>) -> Self {
+        Error {
+            span,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn into_compile_error(self) -> TokenStream {
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+        let mut arguments = Group::new(Delimiter::Brace, TokenTree::from(message).into());
+        arguments.set_span(self.span);
+        [
+            TokenTree::from(Ident::new("compile_error", self.span)),
+            bang.into(),
+            arguments.into(),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+/// Maps every variant of a fieldless enum to a bit position and implements
+/// `bit_manipulation::bit_manipulation::BitOption` for it.
+///
+/// A variant is stored at the position given by its `#[bit(n)]` attribute, or at the position
+/// following the one of the previous variant, starting from 0. The backing integer of the
+/// `EnumSet` is the narrowest one able to hold the highest position. Duplicate positions and
+/// positions above 127 are rejected at compile time.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::EnumSet;
+/// use bit_manipulation::BitOption;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, BitOption)]
+/// enum Permission {
+///     Read,
+///     Write,
+///     #[bit(10)]
+///     Admin,
+/// }
+///
+/// let mut permissions: EnumSet<Permission> = EnumSet::new();
+/// permissions.insert(Permission::Admin);
+/// assert_eq!(permissions.bits().get_value(), 1_u16 << 10);
+/// ```
+///
+/// Variants with fields and duplicate positions do not compile:
+///
+/// ```compile_fail
+/// use bit_manipulation::BitOption;
+///
+/// #[derive(Clone, Copy, BitOption)]
+/// enum Permission {
+///     Read(bool),
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use bit_manipulation::BitOption;
+///
+/// #[derive(Clone, Copy, BitOption)]
+/// enum Permission {
+///     Read,
+///     #[bit(0)]
+///     Write,
+/// }
+/// ```
+#[proc_macro_derive(BitOption, attributes(bit))]
+pub fn derive_bit_option(input: TokenStream) -> TokenStream {
+    match bit_option::parse_enum(input) {
+        Ok(item) => bit_option::expand(&item),
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+/// Packs the fields of a struct in the bits of a single unsigned integer.
+///
+/// The argument of the attribute is the backing integer, from `u8` to `u128`. Each field is
+/// either a `bool`, stored in a single bit, or a `uN` integer stored in `N` bits, with `N` from 1
+/// to 128. Fields are laid out from the least significant bit, in declaration order. The struct
+/// becomes a `Copy` wrapper around a `Bits<T>` with, for each field, a getter, a `set_` setter and
+/// a `with_` builder method. Integer getters return the narrowest unsigned integer able to hold
+/// the field, and integer setters return `BitError::FieldOverflow` if the value does not fit.
+///
+/// Fields that do not fit in the backing integer are rejected at compile time.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::BitError;
+/// use bit_manipulation::bitfield;
+///
+/// #[bitfield(u32)]
+/// struct Header {
+///     version: u3,
+///     compressed: bool,
+///     flags: u4,
+///     len: u24,
+/// }
+///
+/// let mut header = Header::new().with_version(2)?.with_compressed(true);
+/// header.set_len(1500)?;
+/// assert_eq!(header.version(), 2);
+/// assert_eq!(header.len(), 1500);
+/// assert_eq!(header.into_bits().get_value(), 1500 << 8 | 1 << 3 | 2);
+/// assert_eq!(header.set_flags(16), Err(BitError::FieldOverflow { field_width: 4 }));
+/// # Ok::<(), BitError>(())
+/// ```
+///
+/// Fields wider than the backing integer do not compile:
+///
+/// ```compile_fail
+/// use bit_manipulation::bitfield;
+///
+/// #[bitfield(u8)]
+/// struct Header {
+///     version: u3,
+///     len: u6,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn bitfield(arguments: TokenStream, item: TokenStream) -> TokenStream {
+    match bitfield::parse_bitfield(arguments, item).and_then(|item| bitfield::expand(&item)) {
+        Ok(tokens) => tokens,
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+pub(crate) fn split_top_level(stream: TokenStream, separator: char) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    for token in stream {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == separator => parts.push(Vec::new()),
+            _ => parts.last_mut().unwrap().push(token),
+        }
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
@@ -0,0 +1,61 @@
+use bit_manipulation::bit_manipulation::{BitOption, EnumSet};
+use bit_manipulation::BitOption;
+
+#[derive(Debug, Clone, Copy, PartialEq, BitOption)]
+enum Permission {
+    Read,
+    Write,
+    #[bit(6)]
+    Exec,
+    Admin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, BitOption)]
+pub enum Channel {
+    #[bit(100)]
+    Debug,
+    Trace = 3,
+}
+
+#[test]
+fn sequential_and_explicit_positions() {
+    assert_eq!(Permission::Read.position(), 0);
+    assert_eq!(Permission::Write.position(), 1);
+    assert_eq!(Permission::Exec.position(), 6);
+    assert_eq!(Permission::Admin.position(), 7);
+    assert_eq!(Permission::from_position(6), Some(Permission::Exec));
+    assert_eq!(Permission::from_position(2), None);
+    assert_eq!(
+        Permission::VARIANTS,
+        &[
+            Permission::Read,
+            Permission::Write,
+            Permission::Exec,
+            Permission::Admin
+        ]
+    );
+
+    assert_eq!(Channel::Debug.position(), 100);
+    assert_eq!(Channel::Trace.position(), 101);
+}
+
+#[test]
+fn narrowest_repr() {
+    assert_eq!(std::mem::size_of::<EnumSet<Permission>>(), 1);
+    assert_eq!(std::mem::size_of::<EnumSet<Channel>>(), 16);
+}
+
+#[test]
+fn enum_set() {
+    let mut permissions: EnumSet<Permission> =
+        [Permission::Read, Permission::Admin].into_iter().collect();
+    assert!(permissions.contains(Permission::Admin));
+    assert!(!permissions.contains(Permission::Exec));
+    assert_eq!(permissions.bits().get_value(), 0b1000_0001);
+
+    permissions.remove(Permission::Read);
+    permissions.insert(Permission::Exec);
+    assert!(permissions.iter().eq([Permission::Exec, Permission::Admin]));
+
+    assert_eq!(EnumSet::<Channel>::all().len(), 2);
+}
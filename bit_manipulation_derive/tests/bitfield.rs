@@ -0,0 +1,71 @@
+use bit_manipulation::bit_manipulation::{BitError, Bits};
+use bit_manipulation::bitfield;
+
+#[bitfield(u32)]
+#[derive(PartialOrd)]
+pub struct Header {
+    /// The protocol version.
+    pub version: u3,
+    pub(crate) flags: u5,
+    len: u24,
+}
+
+#[bitfield(u128)]
+struct Wide {
+    ready: bool,
+    id: u64,
+    tag: u63,
+}
+
+#[test]
+fn layout_and_accessors() {
+    let mut header = Header::new()
+        .with_version(5)
+        .unwrap()
+        .with_flags(0b1_0011)
+        .unwrap();
+    header.set_len(0xAB_CDEF).unwrap();
+
+    assert_eq!(header.version(), 5);
+    assert_eq!(header.flags(), 0b1_0011);
+    assert_eq!(header.len(), 0xAB_CDEF);
+    assert_eq!(
+        header.into_bits().get_value(),
+        0xAB_CDEF << 8 | 0b1_0011 << 3 | 5
+    );
+    assert_eq!(Header::from_bits(Bits::from_value(0x0000_0107)).len(), 1);
+    assert_eq!(std::mem::size_of::<Header>(), 4);
+    assert_eq!(
+        format!("{header:?}"),
+        "Header { version: 5, flags: 19, len: 11259375 }"
+    );
+}
+
+#[test]
+fn overflowing_values_are_rejected() {
+    let mut header = Header::new().with_len(1).unwrap();
+    assert_eq!(
+        header.set_version(8),
+        Err(BitError::FieldOverflow { field_width: 3 })
+    );
+    assert!(header.with_len(1 << 24).is_err());
+    assert_eq!(header, Header::new().with_len(1).unwrap());
+}
+
+#[test]
+fn bool_and_wide_fields() {
+    let mut wide = Wide::new().with_ready(true);
+    wide.set_id(u64::MAX).unwrap();
+    wide.set_tag(1 << 62).unwrap();
+
+    assert!(wide.ready());
+    assert_eq!(wide.id(), u64::MAX);
+    assert_eq!(wide.tag(), 1 << 62);
+    assert_eq!(
+        wide.into_bits().get_value(),
+        1 << 127 | (u64::MAX as u128) << 1 | 1
+    );
+
+    wide.set_ready(false);
+    assert!(!wide.ready());
+}
@@ -0,0 +1,540 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::hint;
+use core::sync::atomic::{self, Ordering};
+
+use crate::bit_manipulation::{BitError, Bits, Mask};
+use crate::private;
+
+/// A word type that has an atomic counterpart, usable as the `T` of an [`AtomicBits`].
+///
+/// The trait is implemented for `u8` to `u64`, `usize` and their signed counterparts through
+/// the atomic types of [`core::sync::atomic`], when the target supports them. `u128` and `i128`
+/// have no atomic type on stable Rust, so they fall back to a word guarded by a spin lock.
+pub trait AtomicStorage: private::Uint {
+    /// The atomic cell holding a word.
+    type Atomic: Send + Sync;
+
+    /// Creates an atomic cell holding `value`.
+    fn new_atomic(value: Self) -> Self::Atomic;
+
+    /// Consumes the cell and returns the word it holds.
+    fn into_inner(atomic: Self::Atomic) -> Self;
+
+    /// Loads the word, see [`atomic::AtomicU8::load`].
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Stores `value`, see [`atomic::AtomicU8::store`].
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+
+    /// Stores `value` and returns the previous word, see [`atomic::AtomicU8::swap`].
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Bitwise "or" with `value`, returning the previous word.
+    fn fetch_or(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Bitwise "and" with `value`, returning the previous word.
+    fn fetch_and(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Bitwise "xor" with `value`, returning the previous word.
+    fn fetch_xor(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Stores `new` if the word is `current`, see [`atomic::AtomicU8::compare_exchange`].
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+}
+
+macro_rules! _impl_atomic_storage {
+    ($($t:ty => $atomic:ident if $has_atomic:literal),+ $(,)?) => {
+        $(
+            #[cfg(target_has_atomic = $has_atomic)]
+            impl AtomicStorage for $t {
+                type Atomic = atomic::$atomic;
+
+                fn new_atomic(value: Self) -> Self::Atomic {
+                    atomic::$atomic::new(value)
+                }
+                fn into_inner(atomic: Self::Atomic) -> Self {
+                    atomic.into_inner()
+                }
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order)
+                }
+                fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.swap(value, order)
+                }
+                fn fetch_or(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_or(value, order)
+                }
+                fn fetch_and(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_and(value, order)
+                }
+                fn fetch_xor(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_xor(value, order)
+                }
+                fn compare_exchange(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+            }
+        )+
+    };
+}
+
+_impl_atomic_storage![
+    u8 => AtomicU8 if "8",
+    u16 => AtomicU16 if "16",
+    u32 => AtomicU32 if "32",
+    u64 => AtomicU64 if "64",
+    usize => AtomicUsize if "ptr",
+    i8 => AtomicI8 if "8",
+    i16 => AtomicI16 if "16",
+    i32 => AtomicI32 if "32",
+    i64 => AtomicI64 if "64",
+    isize => AtomicIsize if "ptr",
+];
+
+/// A word guarded by a spin lock, the atomic cell of the types without a native atomic.
+///
+/// Every operation holds the lock for a single read-modify-write of the word, and the lock is
+/// taken with `Acquire` and released with `Release` ordering, whatever ordering was requested.
+pub struct SpinLocked<T> {
+    locked: atomic::AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: the word is only accessed while the lock is held, so a shared `SpinLocked<T>` hands
+// out one access at a time, like a mutex.
+unsafe impl<T> Sync for SpinLocked<T> where T: Send {}
+
+impl<T> SpinLocked<T>
+where
+    T: Copy,
+{
+    /// Replaces the word with `update(word)` under the lock and returns the previous word.
+    fn update(&self, update: impl FnOnce(T) -> T) -> T {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        // SAFETY: the lock is held, so no other thread accesses the word.
+        let previous = unsafe { *self.value.get() };
+        // SAFETY: same as above.
+        unsafe { *self.value.get() = update(previous) };
+        self.locked.store(false, Ordering::Release);
+        previous
+    }
+}
+
+macro_rules! _impl_spin_locked_storage {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            #[cfg(target_has_atomic = "8")]
+            impl AtomicStorage for $t {
+                type Atomic = SpinLocked<$t>;
+
+                fn new_atomic(value: Self) -> Self::Atomic {
+                    SpinLocked {
+                        locked: atomic::AtomicBool::new(false),
+                        value: UnsafeCell::new(value),
+                    }
+                }
+                fn into_inner(atomic: Self::Atomic) -> Self {
+                    atomic.value.into_inner()
+                }
+                fn load(atomic: &Self::Atomic, _: Ordering) -> Self {
+                    atomic.update(|value| value)
+                }
+                fn store(atomic: &Self::Atomic, value: Self, _: Ordering) {
+                    atomic.update(|_| value);
+                }
+                fn swap(atomic: &Self::Atomic, value: Self, _: Ordering) -> Self {
+                    atomic.update(|_| value)
+                }
+                fn fetch_or(atomic: &Self::Atomic, value: Self, _: Ordering) -> Self {
+                    atomic.update(|previous| previous | value)
+                }
+                fn fetch_and(atomic: &Self::Atomic, value: Self, _: Ordering) -> Self {
+                    atomic.update(|previous| previous & value)
+                }
+                fn fetch_xor(atomic: &Self::Atomic, value: Self, _: Ordering) -> Self {
+                    atomic.update(|previous| previous ^ value)
+                }
+                fn compare_exchange(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    _: Ordering,
+                    _: Ordering,
+                ) -> Result<Self, Self> {
+                    let previous =
+                        atomic.update(|previous| if previous == current { new } else { previous });
+                    if previous == current {
+                        Ok(previous)
+                    } else {
+                        Err(previous)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+_impl_spin_locked_storage![u128, i128];
+
+/// A [`Bits`] value that can be shared between threads and updated without a lock.
+///
+/// Every operation is a single atomic instruction, or a compare-and-swap loop for the masked
+/// exchange, and takes the memory [`Ordering`] to use explicitly. Single-bit operations return
+/// the previous state of the bit, and mask operations return the previous value.
+///
+/// `u128` and `i128` have no native atomic, so their operations go through a spin lock instead,
+/// see [`AtomicStorage`].
+///
+/// # Example
+///
+/// ```
+/// use std::sync::atomic::Ordering;
+///
+/// use bit_manipulation::bit_manipulation::{AtomicBits, Bits};
+///
+/// let flags = AtomicBits::new(Bits::<u32>::new());
+/// std::thread::scope(|scope| {
+///     for bit in 0..4 {
+///         let flags = &flags;
+///         scope.spawn(move || flags.set_bit(bit, Ordering::Relaxed));
+///     }
+/// });
+/// assert_eq!(flags.load(Ordering::Relaxed).get_value(), 0b1111);
+/// ```
+pub struct AtomicBits<T>
+where
+    T: AtomicStorage,
+{
+    value: T::Atomic,
+}
+
+impl<T> AtomicBits<T>
+where
+    T: AtomicStorage,
+{
+    /// Creates an atomic value holding `bits`.
+    pub fn new(bits: Bits<T>) -> Self {
+        AtomicBits {
+            value: T::new_atomic(bits.get_value()),
+        }
+    }
+
+    /// Consumes the atomic value and returns the bits it holds.
+    pub fn into_inner(self) -> Bits<T> {
+        Bits::from_value(T::into_inner(self.value))
+    }
+
+    /// Loads the current bits.
+    pub fn load(&self, order: Ordering) -> Bits<T> {
+        Bits::from_value(T::load(&self.value, order))
+    }
+
+    /// Replaces the current bits with `bits`.
+    pub fn store(&self, bits: Bits<T>, order: Ordering) {
+        T::store(&self.value, bits.get_value(), order)
+    }
+
+    /// Replaces the current bits with `bits` and returns the previous ones.
+    pub fn swap(&self, bits: Bits<T>, order: Ordering) -> Bits<T> {
+        Bits::from_value(T::swap(&self.value, bits.get_value(), order))
+    }
+
+    /// Checks if a specific bit is set in the current value.
+    ///
+    /// Out of range positions are reported as not set, like [`Bits::is_bit_on`] does.
+    pub fn is_bit_on(&self, bit: u8, order: Ordering) -> bool {
+        self.load(order).is_bit_on(bit)
+    }
+
+    /// Sets a specific bit.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(previous)` with the state of the bit before the call.
+    /// - `Err(BitError::OutOfRange)` if `bit` is greater than or equal to the bit width of `T`, in
+    ///   which case the value is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use bit_manipulation::bit_manipulation::{AtomicBits, Bits};
+    ///
+    /// let flags = AtomicBits::new(Bits::<u8>::new());
+    /// assert_eq!(flags.set_bit(3, Ordering::AcqRel), Ok(false));
+    /// assert_eq!(flags.set_bit(3, Ordering::AcqRel), Ok(true));
+    /// assert!(flags.set_bit(8, Ordering::AcqRel).is_err());
+    /// ```
+    pub fn set_bit(&self, bit: u8, order: Ordering) -> Result<bool, BitError> {
+        Bits::<T>::check_bit(bit)?;
+        let previous = T::fetch_or(&self.value, T::convert(bit), order);
+        Ok(Bits::from_value(previous).is_bit_on(bit))
+    }
+
+    /// Clears a specific bit.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(previous)` with the state of the bit before the call.
+    /// - `Err(BitError::OutOfRange)` if `bit` is greater than or equal to the bit width of `T`.
+    pub fn clear_bit(&self, bit: u8, order: Ordering) -> Result<bool, BitError> {
+        Bits::<T>::check_bit(bit)?;
+        let previous = T::fetch_and(&self.value, !T::convert(bit), order);
+        Ok(Bits::from_value(previous).is_bit_on(bit))
+    }
+
+    /// Flips a specific bit.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(previous)` with the state of the bit before the call.
+    /// - `Err(BitError::OutOfRange)` if `bit` is greater than or equal to the bit width of `T`.
+    pub fn toggle_bit(&self, bit: u8, order: Ordering) -> Result<bool, BitError> {
+        Bits::<T>::check_bit(bit)?;
+        let previous = T::fetch_xor(&self.value, T::convert(bit), order);
+        Ok(Bits::from_value(previous).is_bit_on(bit))
+    }
+
+    /// Turns on every bit selected by `mask` and returns the previous value.
+    pub fn fetch_or(&self, mask: Mask<T>, order: Ordering) -> Bits<T> {
+        Bits::from_value(T::fetch_or(&self.value, mask.value(), order))
+    }
+
+    /// Keeps only the bits selected by `mask` and returns the previous value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use bit_manipulation::bit_manipulation::{AtomicBits, Bits, Mask};
+    ///
+    /// let flags = AtomicBits::new(Bits::from_value(0b1111_u8));
+    /// let previous = flags.fetch_and(!Mask::from_value(0b0110), Ordering::AcqRel);
+    /// assert_eq!(previous.get_value(), 0b1111);
+    /// assert_eq!(flags.load(Ordering::Acquire).get_value(), 0b1001);
+    /// ```
+    pub fn fetch_and(&self, mask: Mask<T>, order: Ordering) -> Bits<T> {
+        Bits::from_value(T::fetch_and(&self.value, mask.value(), order))
+    }
+
+    /// Flips every bit selected by `mask` and returns the previous value.
+    pub fn fetch_xor(&self, mask: Mask<T>, order: Ordering) -> Bits<T> {
+        Bits::from_value(T::fetch_xor(&self.value, mask.value(), order))
+    }
+
+    /// Stores `new` if the current value is `current`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(previous)` if the value was `current` and has been replaced.
+    /// - `Err(actual)` with the current value otherwise.
+    pub fn compare_exchange(
+        &self,
+        current: Bits<T>,
+        new: Bits<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Bits<T>, Bits<T>> {
+        T::compare_exchange(
+            &self.value,
+            current.get_value(),
+            new.get_value(),
+            success,
+            failure,
+        )
+        .map(Bits::from_value)
+        .map_err(Bits::from_value)
+    }
+
+    /// Replaces the bits selected by `mask` with those of `new`, if they match those of
+    /// `expected`.
+    ///
+    /// Bits outside of the mask are neither compared nor changed, so concurrent updates to other
+    /// bits do not make the exchange fail: the compare-and-swap is retried until it succeeds or
+    /// the selected bits stop matching.
+    ///
+    /// # Parameters
+    ///
+    /// - `mask`: The bits to compare and replace.
+    /// - `expected`: The value the selected bits must match, see [`Bits::masked_eq`].
+    /// - `new`: The value the selected bits are replaced with.
+    /// - `success`: The ordering of the exchange when it succeeds.
+    /// - `failure`: The ordering of the loads, and of the exchange when it fails.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(previous)` with the whole value before the exchange.
+    /// - `Err(actual)` with the whole current value if the selected bits do not match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use bit_manipulation::bit_manipulation::{AtomicBits, Bits, Mask};
+    ///
+    /// let state = Mask::from_value(0b0011_u8);
+    /// let flags = AtomicBits::new(Bits::from_value(0b1000_0001_u8));
+    ///
+    /// // Move the state from 1 to 2, leaving the other bits alone.
+    /// let exchange = flags.compare_exchange_masked(state, 1, 2, Ordering::AcqRel, Ordering::Acquire);
+    /// assert_eq!(exchange.map(|bits| bits.get_value()), Ok(0b1000_0001));
+    /// assert_eq!(flags.load(Ordering::Acquire).get_value(), 0b1000_0010);
+    ///
+    /// let exchange = flags.compare_exchange_masked(state, 1, 3, Ordering::AcqRel, Ordering::Acquire);
+    /// assert!(exchange.is_err());
+    /// ```
+    pub fn compare_exchange_masked(
+        &self,
+        mask: Mask<T>,
+        expected: T,
+        new: T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Bits<T>, Bits<T>> {
+        let mut current = self.load(failure);
+        loop {
+            if !current.masked_eq(mask, expected) {
+                return Err(current);
+            }
+            let mut replaced = current;
+            replaced.clear_mask(mask);
+            replaced.apply_mask(Mask::from_value(new & mask.value()));
+            match self.compare_exchange(current, replaced, success, failure) {
+                Ok(previous) => return Ok(previous),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl<T> Default for AtomicBits<T>
+where
+    T: AtomicStorage,
+{
+    fn default() -> Self {
+        AtomicBits::new(Bits::new())
+    }
+}
+
+impl<T> From<Bits<T>> for AtomicBits<T>
+where
+    T: AtomicStorage,
+{
+    fn from(bits: Bits<T>) -> Self {
+        AtomicBits::new(bits)
+    }
+}
+
+impl<T> fmt::Debug for AtomicBits<T>
+where
+    T: AtomicStorage + fmt::Debug,
+{
+    /// Formats the current value, loaded with `Relaxed` ordering.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AtomicBits")
+            .field(&self.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_bit_operations() {
+        let flags = AtomicBits::new(Bits::from_value(0b0100_i16));
+        assert_eq!(flags.set_bit(15, Ordering::SeqCst), Ok(false));
+        assert_eq!(flags.clear_bit(2, Ordering::SeqCst), Ok(true));
+        assert_eq!(flags.clear_bit(2, Ordering::SeqCst), Ok(false));
+        assert_eq!(flags.toggle_bit(0, Ordering::SeqCst), Ok(false));
+        assert_eq!(
+            flags.toggle_bit(16, Ordering::SeqCst),
+            Err(BitError::OutOfRange { bit: 16, width: 16 })
+        );
+        assert!(flags.is_bit_on(15, Ordering::SeqCst));
+        assert_eq!(flags.into_inner().get_value(), i16::MIN | 1);
+    }
+
+    #[test]
+    fn spin_locked_fallback() {
+        let flags = AtomicBits::<u128>::default();
+        assert_eq!(flags.set_bit(127, Ordering::SeqCst), Ok(false));
+        assert_eq!(
+            flags
+                .fetch_or(Mask::from_value(0b11), Ordering::SeqCst)
+                .get_value(),
+            1 << 127
+        );
+        assert_eq!(
+            flags.compare_exchange(
+                Bits::new(),
+                Bits::from_value(1),
+                Ordering::SeqCst,
+                Ordering::SeqCst
+            ),
+            Err(Bits::from_value(1 << 127 | 0b11))
+        );
+        assert_eq!(
+            flags.swap(Bits::new(), Ordering::SeqCst).iter_ones().len(),
+            3
+        );
+        assert_eq!(flags.load(Ordering::SeqCst), Bits::new());
+    }
+
+    #[test]
+    fn concurrent_masked_exchange() {
+        let flags = AtomicBits::new(Bits::<u64>::new());
+        let counter = Mask::from_value(0xFF_u64);
+        std::thread::scope(|scope| {
+            for thread in 0..8_u8 {
+                let flags = &flags;
+                scope.spawn(move || {
+                    flags.set_bit(8 + thread, Ordering::Relaxed).unwrap();
+                    for _ in 0..16 {
+                        let mut current = flags.load(Ordering::Acquire);
+                        loop {
+                            let count = current.get_value() & counter.value();
+                            match flags.compare_exchange_masked(
+                                counter,
+                                count,
+                                count + 1,
+                                Ordering::AcqRel,
+                                Ordering::Acquire,
+                            ) {
+                                Ok(_) => break,
+                                Err(actual) => current = actual,
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(flags.load(Ordering::Acquire).get_value(), 0xFF80);
+    }
+}
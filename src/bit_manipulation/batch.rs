@@ -0,0 +1,233 @@
+use core::iter::FusedIterator;
+
+use crate::bit_manipulation::{Bits, IterOnes};
+use crate::private;
+
+/// The outcome of a batch operation such as [`Bits::set_bits`], for each requested position.
+///
+/// Outcomes are reported per distinct position, by comparing the value before the batch with
+/// the value after it, so every requested position ends up in exactly one of three groups even
+/// when it is listed several times:
+///
+/// - applied: the bit differs from its state before the batch.
+/// - unchanged: the bit has the same state as before the batch, either because it was already in
+///   the requested state or because the batch flipped it back, as toggling a position twice does.
+/// - rejected: the position is out of range for `T`, so it was skipped.
+///
+/// Applied and unchanged positions are always in range, so they are stored as a [`Bits`] value.
+/// Rejected positions are kept in a bitmap over every `u8` position, so a batch never allocates
+/// and is available without the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::Bits;
+///
+/// let mut bits = Bits::from_value(0b0010_u8);
+/// let result = bits.set_bits([0, 1, 9]);
+/// assert!(result.applied().iter_ones().eq([0]));
+/// assert!(result.unchanged().iter_ones().eq([1]));
+/// assert!(result.rejected().eq([9]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResult<T>
+where
+    T: private::Uint,
+{
+    applied: Bits<T>,
+    unchanged: Bits<T>,
+    /// The rejected positions below 128, then those from 128.
+    rejected: [Bits<u128>; 2],
+}
+
+impl<T> BatchResult<T>
+where
+    T: private::Uint,
+{
+    /// Returns the positions whose bit was changed by the batch.
+    pub fn applied(&self) -> Bits<T> {
+        self.applied
+    }
+
+    /// Returns the positions whose bit was already in the requested state.
+    pub fn unchanged(&self) -> Bits<T> {
+        self.unchanged
+    }
+
+    /// Returns the out of range positions, in ascending order and each of them once.
+    pub fn rejected(&self) -> Rejected {
+        Rejected {
+            low: self.rejected[0].iter_ones(),
+            high: self.rejected[1].iter_ones(),
+        }
+    }
+
+    /// Returns `true` if no position was rejected.
+    pub fn is_fully_applied(&self) -> bool {
+        self.rejected == [Bits::new(); 2]
+    }
+}
+
+/// An iterator over the rejected positions of a [`BatchResult`], in ascending order.
+///
+/// This struct is created by [`BatchResult::rejected`].
+#[derive(Debug, Clone)]
+pub struct Rejected {
+    low: IterOnes<u128>,
+    high: IterOnes<u128>,
+}
+
+impl Iterator for Rejected {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.low.next().or_else(|| Some(self.high.next()? + 128))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.low.len() + self.high.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Rejected {
+    fn next_back(&mut self) -> Option<u8> {
+        match self.high.next_back() {
+            Some(bit) => Some(bit + 128),
+            None => self.low.next_back(),
+        }
+    }
+}
+
+impl ExactSizeIterator for Rejected {}
+
+impl FusedIterator for Rejected {}
+
+/// The outcome of a batch operation on a set addressed by `usize` positions, such as
+/// [`BitArray::set_bits`](super::BitArray::set_bits) or `BitVec::set_bits`.
+///
+/// Positions are grouped as in [`BatchResult`]: applied positions had their bit changed by the
+/// batch, unchanged positions were already in the requested state, and rejected positions were
+/// skipped. Applied and unchanged positions are stored in a set of type `S`, as long as the
+/// set the batch ran on. Rejected positions are addressed by `usize`, so only their number and
+/// the lowest of them are kept, and the outcome never allocates on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchOutcome<S> {
+    pub(super) applied: S,
+    pub(super) unchanged: S,
+    rejected: usize,
+    first_rejected: Option<usize>,
+}
+
+impl<S> BatchOutcome<S> {
+    pub(crate) fn new(applied: S, unchanged: S) -> Self {
+        BatchOutcome {
+            applied,
+            unchanged,
+            rejected: 0,
+            first_rejected: None,
+        }
+    }
+
+    /// Records a skipped position. Repeated positions are counted each time they are requested.
+    pub(crate) fn reject(&mut self, bit: usize) {
+        self.rejected += 1;
+        self.first_rejected = Some(self.first_rejected.map_or(bit, |first| first.min(bit)));
+    }
+
+    /// Returns the positions whose bit was changed by the batch.
+    pub fn applied(&self) -> &S {
+        &self.applied
+    }
+
+    /// Returns the positions whose bit was already in the requested state.
+    pub fn unchanged(&self) -> &S {
+        &self.unchanged
+    }
+
+    /// Returns the number of requested positions that were skipped, counting repetitions.
+    pub fn rejected_count(&self) -> usize {
+        self.rejected
+    }
+
+    /// Returns the lowest skipped position, if any.
+    pub fn first_rejected(&self) -> Option<usize> {
+        self.first_rejected
+    }
+
+    /// Returns `true` if no position was rejected.
+    pub fn is_fully_applied(&self) -> bool {
+        self.rejected == 0
+    }
+}
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Runs `operation` on every requested position and records the outcome of the batch.
+    ///
+    /// `operation` is only called with in range positions. The applied positions are those whose
+    /// bit differs from the value before the batch.
+    pub(crate) fn apply_batch<I>(
+        &mut self,
+        bits: I,
+        mut operation: impl FnMut(&mut Self, u8),
+    ) -> BatchResult<T>
+    where
+        I: IntoIterator,
+        I::Item: Into<u8>,
+    {
+        let before = *self;
+        let mut requested = Bits::new();
+        let mut rejected = [Bits::new(); 2];
+        for bit in bits {
+            let bit = bit.into();
+            if bit >= T::in_memory_size() {
+                rejected[bit as usize / 128].set_bit(bit % 128);
+            } else {
+                operation(self, bit);
+                requested.set_bit(bit);
+            }
+        }
+        let applied = before ^ *self;
+        BatchResult {
+            applied,
+            unchanged: requested - applied,
+            rejected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_outcomes() {
+        let mut bits: Bits<u16> = Bits::new();
+
+        let result = bits.set_bits(0..4_u8);
+        assert_eq!(result.applied().get_value(), 0b1111);
+        assert!(result.is_fully_applied());
+
+        let result = bits.clear_bits(vec![2_u8, 3, 2, 7, 16, 200]);
+        assert_eq!(result.applied().get_value(), 0b1100);
+        assert_eq!(result.unchanged().get_value(), 0b1000_0000);
+        assert!(result.rejected().eq([16, 200]));
+        assert!(result.rejected().rev().eq([200, 16]));
+        assert!(!result.is_fully_applied());
+        assert_eq!(bits.get_value(), 0b0011);
+
+        let result = bits.toggle_bits([true, false].map(u8::from));
+        assert_eq!(result.applied().get_value(), 0b11);
+        assert_eq!(bits.get_value(), 0);
+
+        let result = bits.toggle_bits([255_u8, 3, 5, 3, 127, 255, 128]);
+        assert!(result.rejected().eq([127, 128, 255]));
+        assert_eq!(result.rejected().len(), 3);
+        assert_eq!(result.applied().get_value(), 0b10_0000);
+        assert_eq!(result.unchanged().get_value(), 0b1000);
+        assert_eq!(bits.get_value(), 0b10_0000);
+    }
+}
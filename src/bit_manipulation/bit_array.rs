@@ -0,0 +1,1132 @@
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound, Not, RangeBounds, Sub,
+    SubAssign,
+};
+
+use crate::bit_manipulation::words::{word_bit, WORD_BITS};
+use crate::bit_manipulation::{
+    ArrayIterOnes, ArrayIterZeros, BatchOutcome, BitError, WordBitsOn, WordView,
+};
+
+/// Returns the number of `u64` words needed to store `bits` bits.
+///
+/// This is the value expected for the `W` parameter of [`BitArray`], for example
+/// `BitArray<200, { words_for(200) }>`.
+pub const fn words_for(bits: usize) -> usize {
+    bits.div_ceil(WORD_BITS)
+}
+
+/// A fixed-capacity, stack-allocated sequence of exactly `N` bits.
+///
+/// `BitArray` fills the gap between [`Bits<u128>`](crate::bit_manipulation::Bits) and the
+/// heap-backed `BitVec`: it is `Copy` and never allocates. It offers the same surface as
+/// `Bits<T>` (single bits and batches, toggling, ranges, iterators, set algebra, masks and the
+/// `try_` and `strict_` functions), with `usize` positions ranging from 0 to `N - 1`.
+///
+/// The bits are stored in `W` words of type `u64`. Stable Rust cannot yet compute the array
+/// length from `N` in the type itself, so `W` has to be spelled out and must be equal to
+/// [`words_for(N)`](words_for); any other value is rejected at compile time. It defaults to 1,
+/// so `BitArray<N>` can be written directly for `N <= 64`. Any bit at or beyond `N` is always
+/// kept cleared, so two arrays with the same activated bits always compare equal.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::{words_for, BitArray};
+///
+/// let mut bits: BitArray<200, { words_for(200) }> = BitArray::new();
+/// bits.set_bit(63);
+/// bits.set_bit(64);
+/// bits.set_bit(199);
+/// assert!(!bits.set_bit(200));
+/// assert_eq!(bits.get_value(), [1 << 63, 1, 0, 1 << 7]);
+/// ```
+///
+/// A word count that does not match `N` does not compile:
+///
+/// ```compile_fail
+/// use bit_manipulation::bit_manipulation::BitArray;
+///
+/// let bits: BitArray<100> = BitArray::new();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitArray<const N: usize, const W: usize = 1> {
+    words: [u64; W],
+}
+
+impl<const N: usize, const W: usize> BitArray<N, W> {
+    const WORDS_MATCH_LEN: () = assert!(
+        W == words_for(N),
+        "the word count `W` of a `BitArray<N, W>` must be equal to `words_for(N)`"
+    );
+
+    /// Creates a new instance of the `BitArray` struct with all `N` bits set to 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitArray;
+    ///
+    /// let bits: BitArray<40> = BitArray::new();
+    /// assert_eq!(bits.get_value(), [0]);
+    /// ```
+    pub const fn new() -> Self {
+        let () = Self::WORDS_MATCH_LEN;
+        BitArray { words: [0; W] }
+    }
+
+    /// Returns the number of addressable bits, `N`.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the array has no addressable bits, that is when `N` is 0.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Checks if a specific bit is set (activated) within the array.
+    ///
+    /// # Parameters
+    ///
+    /// - `bit`: The position of the bit to check, ranging from 0 to `N - 1`.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the specified bit is set (activated).
+    /// - `false` if the provided bit position is out of range (greater than or equal to `N`)
+    ///   or if the bit is not set.
+    pub fn is_bit_on(&self, bit: usize) -> bool {
+        bit < N && word_bit(&self.words, bit)
+    }
+
+    /// Checks if specific bits are set (activated) within the array.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to check, such as an array, a range or an iterator.
+    ///
+    /// # Returns
+    ///
+    /// A lazy iterator yielding, for each requested position in order, `true` if the bit is set
+    /// and `false` otherwise. Out of range positions are reported as not set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitArray;
+    ///
+    /// let mut bits: BitArray<10> = BitArray::new();
+    /// bits.set_bit(3);
+    /// assert!(bits.are_bits_on([3_usize, 4, 65]).eq([true, false, false]));
+    /// ```
+    pub fn are_bits_on<I>(&self, bits: I) -> WordBitsOn<'_, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        WordBitsOn::new(&self.words, N, bits.into_iter())
+    }
+
+    /// Sets a specific bit within the array.
+    ///
+    /// # Parameters
+    ///
+    /// - `bit`: The position of the bit to set, ranging from 0 to `N - 1`.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the specified bit was successfully set.
+    /// - `false` if the provided bit position is out of range (greater than or equal to `N`).
+    pub fn set_bit(&mut self, bit: usize) -> bool {
+        if bit >= N {
+            return false;
+        }
+        self.words[bit / WORD_BITS] |= 1 << (bit % WORD_BITS);
+        true
+    }
+
+    /// Sets multiple bits within the array, skipping the positions that are out of range.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to set, such as an array, a range or an iterator.
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchOutcome`] holding the positions turned on by this call, the positions that
+    /// were already set, and the out of range positions that were skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitArray;
+    ///
+    /// let mut bits: BitArray<10> = BitArray::new();
+    /// bits.set_bit(1);
+    /// let outcome = bits.set_bits([1_usize, 3, 65]);
+    /// assert!(outcome.applied().iter_ones().eq([3]));
+    /// assert!(outcome.unchanged().iter_ones().eq([1]));
+    /// assert_eq!(outcome.first_rejected(), Some(65));
+    /// ```
+    pub fn set_bits<I>(&mut self, bits: I) -> BatchOutcome<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        self.apply_batch(bits, |array, bit| {
+            array.set_bit(bit);
+        })
+    }
+
+    /// Clears a specific bit within the array, setting it to 0.
+    ///
+    /// # Parameters
+    ///
+    /// - `bit`: The position of the bit to clear, ranging from 0 to `N - 1`.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the specified bit was successfully cleared.
+    /// - `false` if the provided bit position is out of range (greater than or equal to `N`).
+    pub fn clear_bit(&mut self, bit: usize) -> bool {
+        if bit >= N {
+            return false;
+        }
+        self.words[bit / WORD_BITS] &= !(1 << (bit % WORD_BITS));
+        true
+    }
+
+    /// Clears multiple bits within the array, skipping the positions that are out of range.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to clear, such as an array, a range or an iterator.
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchOutcome`] holding the positions turned off by this call, the positions that
+    /// were already cleared, and the out of range positions that were skipped.
+    pub fn clear_bits<I>(&mut self, bits: I) -> BatchOutcome<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        self.apply_batch(bits, |array, bit| {
+            array.clear_bit(bit);
+        })
+    }
+
+    /// Flips a specific bit within the array.
+    ///
+    /// # Parameters
+    ///
+    /// - `bit`: The position of the bit to flip, ranging from 0 to `N - 1`.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the specified bit was successfully flipped.
+    /// - `false` if the provided bit position is out of range (greater than or equal to `N`).
+    pub fn toggle_bit(&mut self, bit: usize) -> bool {
+        if bit >= N {
+            return false;
+        }
+        self.words[bit / WORD_BITS] ^= 1 << (bit % WORD_BITS);
+        true
+    }
+
+    /// Flips multiple bits within the array, skipping the positions that are out of range.
+    ///
+    /// A position listed an even number of times is flipped back, so it is reported as
+    /// unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to flip, such as an array, a range or an iterator.
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchOutcome`] holding the positions whose bit was flipped by this call, the
+    /// positions flipped back to their initial state, and the out of range positions that were
+    /// skipped.
+    pub fn toggle_bits<I>(&mut self, bits: I) -> BatchOutcome<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        self.apply_batch(bits, |array, bit| {
+            array.toggle_bit(bit);
+        })
+    }
+
+    /// Flips all `N` bits. The unused high bits of the last word are left cleared.
+    pub fn invert(&mut self) {
+        *self = self.complement();
+    }
+
+    /// Clears all bits, setting them to 0.
+    pub fn clear_all_bits(&mut self) {
+        self.words = [0; W];
+    }
+
+    /// Returns the underlying `u64` words, least significant word first.
+    pub fn get_value(&self) -> [u64; W] {
+        self.words
+    }
+
+    /// Returns a lazy view over the state of every bit in the array, from position 0 to
+    /// `N - 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitArray;
+    ///
+    /// let mut bits: BitArray<4> = BitArray::new();
+    /// bits.set_bit(2);
+    /// assert!(bits.get_all_bits().eq([false, false, true, false]));
+    /// ```
+    pub fn get_all_bits(&self) -> WordView<'_> {
+        WordView::new(&self.words, N)
+    }
+
+    /// Sets all `N` bits to 1. The unused high bits of the last word are left cleared.
+    pub fn set_all_flags(&mut self) {
+        self.words = [u64::MAX; W];
+        self.clear_tail();
+    }
+
+    /// Returns the number of bits that are set.
+    pub fn count_ones(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Returns the number of bits that are not set, out of the `N` addressable bits.
+    pub fn count_zeros(&self) -> u32 {
+        N as u32 - self.count_ones()
+    }
+
+    /// Returns an iterator over the positions of the bits that are set, in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{words_for, BitArray};
+    ///
+    /// let bits: BitArray<130, { words_for(130) }> = [1, 64, 129].into_iter().collect();
+    /// assert!(bits.iter_ones().eq([1, 64, 129]));
+    /// assert!(bits.iter_ones().rev().eq([129, 64, 1]));
+    /// assert_eq!(bits.iter_ones().len(), 3);
+    /// ```
+    pub fn iter_ones(&self) -> ArrayIterOnes<W> {
+        ArrayIterOnes::new(self.words)
+    }
+
+    /// Returns an iterator over the positions of the bits that are not set, in ascending order.
+    ///
+    /// Only the `N` addressable positions are yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitArray;
+    ///
+    /// let mut bits: BitArray<4> = BitArray::new();
+    /// bits.set_bits([0_usize, 2]);
+    /// assert!(bits.iter_zeros().eq([1, 3]));
+    /// ```
+    pub fn iter_zeros(&self) -> ArrayIterZeros<W> {
+        ArrayIterZeros(ArrayIterOnes::new(self.complement().words))
+    }
+
+    /// Checks if a specific bit is set (activated), rejecting out of range positions.
+    ///
+    /// This is the fallible counterpart of [`BitArray::is_bit_on`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the specified bit is set, `Ok(false)` if it is not.
+    /// - `Err(BitError::OutOfRange)` if the provided bit position is greater than or equal to `N`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitArray, BitError};
+    ///
+    /// let bits: BitArray<10> = BitArray::new();
+    /// assert_eq!(bits.try_is_bit_on(2), Ok(false));
+    /// assert_eq!(bits.try_is_bit_on(10), Err(BitError::OutOfRange { bit: 10, width: 10 }));
+    /// ```
+    pub fn try_is_bit_on(&self, bit: usize) -> Result<bool, BitError> {
+        Self::check_bit(bit)?;
+        Ok(self.is_bit_on(bit))
+    }
+
+    /// Sets a specific bit, rejecting out of range positions.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the specified bit was successfully set.
+    /// - `Err(BitError::OutOfRange)` if the provided bit position is greater than or equal to `N`,
+    ///   in which case the array is left unchanged.
+    pub fn try_set_bit(&mut self, bit: usize) -> Result<(), BitError> {
+        Self::check_bit(bit)?;
+        self.set_bit(bit);
+        Ok(())
+    }
+
+    /// Sets multiple bits, rejecting the whole batch if any position is out of range.
+    ///
+    /// Every position is validated before the array is modified, so either all the bits are
+    /// set or none of them is.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all the specified bits were successfully set.
+    /// - `Err(BitError::BatchOutOfRange)` listing every rejected position otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitArray, BitError};
+    ///
+    /// let mut bits: BitArray<10> = BitArray::new();
+    /// let error = bits.try_set_bits([1_usize, 12, 3, 10]).unwrap_err();
+    /// assert_eq!(error, BitError::BatchOutOfRange { bits: vec![12, 10], width: 10 });
+    /// assert_eq!(bits.count_ones(), 0);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn try_set_bits<I>(&mut self, bits: I) -> Result<(), BitError>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        *self |= Self::check_bits(bits)?;
+        Ok(())
+    }
+
+    /// Clears a specific bit, rejecting out of range positions.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the specified bit was successfully cleared.
+    /// - `Err(BitError::OutOfRange)` if the provided bit position is greater than or equal to `N`,
+    ///   in which case the array is left unchanged.
+    pub fn try_clear_bit(&mut self, bit: usize) -> Result<(), BitError> {
+        Self::check_bit(bit)?;
+        self.clear_bit(bit);
+        Ok(())
+    }
+
+    /// Clears multiple bits, rejecting the whole batch if any position is out of range.
+    ///
+    /// Every position is validated before the array is modified, so either all the bits are
+    /// cleared or none of them is.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all the specified bits were successfully cleared.
+    /// - `Err(BitError::BatchOutOfRange)` listing every rejected position otherwise.
+    #[cfg(feature = "alloc")]
+    pub fn try_clear_bits<I>(&mut self, bits: I) -> Result<(), BitError>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        *self -= Self::check_bits(bits)?;
+        Ok(())
+    }
+
+    /// Checks if a specific bit is set (activated), panicking on out of range positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is greater than or equal to `N`.
+    #[track_caller]
+    pub fn strict_is_bit_on(&self, bit: usize) -> bool {
+        self.try_is_bit_on(bit)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Sets a specific bit, panicking on out of range positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is greater than or equal to `N`.
+    #[track_caller]
+    pub fn strict_set_bit(&mut self, bit: usize) {
+        self.try_set_bit(bit)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Sets multiple bits, panicking if any position is out of range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any position is greater than or equal to `N`. The panic message lists every
+    /// rejected position and the array is left unchanged.
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub fn strict_set_bits<I>(&mut self, bits: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        self.try_set_bits(bits)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Clears a specific bit, panicking on out of range positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is greater than or equal to `N`.
+    #[track_caller]
+    pub fn strict_clear_bit(&mut self, bit: usize) {
+        self.try_clear_bit(bit)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Clears multiple bits, panicking if any position is out of range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any position is greater than or equal to `N`. The panic message lists every
+    /// rejected position and the array is left unchanged.
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub fn strict_clear_bits<I>(&mut self, bits: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        self.try_clear_bits(bits)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Turns on every bit of a range.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The positions to turn on, such as `2..5`, `60..=70` or `100..`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the bits were successfully turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond `N`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{words_for, BitArray};
+    ///
+    /// let mut bits: BitArray<130, { words_for(130) }> = BitArray::new();
+    /// bits.set_range(62..66).unwrap();
+    /// assert_eq!(bits.get_value(), [0b11 << 62, 0b11, 0]);
+    /// assert!(bits.set_range(..131).is_err());
+    /// ```
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>) -> Result<(), BitError> {
+        *self |= Self::range_mask(range)?;
+        Ok(())
+    }
+
+    /// Turns off every bit of a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the bits were successfully turned off.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond `N`.
+    pub fn clear_range(&mut self, range: impl RangeBounds<usize>) -> Result<(), BitError> {
+        *self -= Self::range_mask(range)?;
+        Ok(())
+    }
+
+    /// Flips every bit of a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the bits were successfully flipped.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond `N`.
+    pub fn toggle_range(&mut self, range: impl RangeBounds<usize>) -> Result<(), BitError> {
+        *self ^= Self::range_mask(range)?;
+        Ok(())
+    }
+
+    /// Counts the bits turned on in a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(count)` holding the number of bits turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond `N`.
+    pub fn count_range(&self, range: impl RangeBounds<usize>) -> Result<u32, BitError> {
+        Ok((*self & Self::range_mask(range)?).count_ones())
+    }
+
+    /// Returns `Ok(true)` if at least one bit of the range is turned on.
+    ///
+    /// An empty range holds no bit, so it yields `Ok(false)`.
+    pub fn any_in_range(&self, range: impl RangeBounds<usize>) -> Result<bool, BitError> {
+        Ok(!self.is_disjoint(&Self::range_mask(range)?))
+    }
+
+    /// Returns `Ok(true)` if every bit of the range is turned on.
+    ///
+    /// An empty range holds no bit, so it yields `Ok(true)`.
+    pub fn all_in_range(&self, range: impl RangeBounds<usize>) -> Result<bool, BitError> {
+        Ok(self.is_superset(&Self::range_mask(range)?))
+    }
+
+    /// Returns the lowest position turned on in a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(bit))` holding the lowest position turned on.
+    /// - `Ok(None)` if no bit of the range is turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond `N`.
+    pub fn first_set_in_range(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> Result<Option<usize>, BitError> {
+        Ok((*self & Self::range_mask(range)?).iter_ones().next())
+    }
+
+    /// Returns the highest position turned on in a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(bit))` holding the highest position turned on.
+    /// - `Ok(None)` if no bit of the range is turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond `N`.
+    pub fn last_set_in_range(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> Result<Option<usize>, BitError> {
+        Ok((*self & Self::range_mask(range)?).iter_ones().next_back())
+    }
+
+    /// Returns the bits that are set in `self`, in `other`, or in both.
+    ///
+    /// This is equivalent to the `|` operator.
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a | b)
+    }
+
+    /// Returns the bits that are set in both `self` and `other`.
+    ///
+    /// This is equivalent to the `&` operator.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & b)
+    }
+
+    /// Returns the bits that are set in `self` but not in `other`.
+    ///
+    /// This is equivalent to the `-` operator.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & !b)
+    }
+
+    /// Returns the bits that are set in exactly one of `self` and `other`.
+    ///
+    /// This is equivalent to the `^` operator.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a ^ b)
+    }
+
+    /// Returns the bits that are not set in `self`, out of the `N` addressable bits.
+    ///
+    /// This is equivalent to the `!` operator.
+    pub fn complement(&self) -> Self {
+        let mut complement = self.zip_words(self, |a, _| !a);
+        complement.clear_tail();
+        complement
+    }
+
+    /// Returns `true` if every bit set in `self` is also set in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other) == Self::new()
+    }
+
+    /// Returns `true` if every bit set in `other` is also set in `self`.
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` and `other` have no set bit in common.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other) == Self::new()
+    }
+
+    /// Checks if all the bits at the given positions are set.
+    ///
+    /// An out of range position is never set, so it makes the method return `false`. An empty
+    /// list of positions is trivially contained.
+    pub fn contains_all<I>(&self, bits: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        bits.into_iter().all(|bit| self.is_bit_on(bit.into()))
+    }
+
+    /// Checks if at least one of the bits at the given positions is set.
+    ///
+    /// Out of range positions are never set. An empty list of positions is never contained.
+    pub fn contains_any<I>(&self, bits: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        bits.into_iter().any(|bit| self.is_bit_on(bit.into()))
+    }
+
+    /// Turns on every bit selected by `mask`.
+    ///
+    /// A `BitArray` is its own mask type: build the mask once, for example with
+    /// [`BitArray::set_range`] or by collecting positions, then apply it as often as needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{words_for, BitArray};
+    ///
+    /// let mask: BitArray<100, { words_for(100) }> = [1, 70].into_iter().collect();
+    /// let mut bits = BitArray::new();
+    /// bits.apply_mask(mask);
+    /// assert_eq!(bits.get_value(), [0b10, 1 << 6]);
+    /// ```
+    pub fn apply_mask(&mut self, mask: Self) {
+        *self |= mask;
+    }
+
+    /// Turns off every bit selected by `mask`.
+    pub fn clear_mask(&mut self, mask: Self) {
+        *self -= mask;
+    }
+
+    /// Flips every bit selected by `mask`.
+    pub fn toggle_mask(&mut self, mask: Self) {
+        *self ^= mask;
+    }
+
+    /// Compares only the bits selected by `mask` with the same bits of `expected`.
+    ///
+    /// Bits outside of the mask are ignored, both in the stored value and in `expected`.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if every selected bit of the array equals the same bit of `expected`.
+    /// - `false` otherwise.
+    pub fn masked_eq(&self, mask: Self, expected: Self) -> bool {
+        *self & mask == expected & mask
+    }
+
+    /// Runs `operation` on every in range position and records the outcome of the batch.
+    ///
+    /// The applied positions are those whose bit differs from the array before the batch, as
+    /// for the batches of `Bits<T>`.
+    fn apply_batch<I>(
+        &mut self,
+        bits: I,
+        mut operation: impl FnMut(&mut Self, usize),
+    ) -> BatchOutcome<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        let before = *self;
+        let mut requested = Self::new();
+        let mut outcome = BatchOutcome::new(Self::new(), Self::new());
+        for bit in bits {
+            let bit = bit.into();
+            if bit >= N {
+                outcome.reject(bit);
+            } else {
+                operation(self, bit);
+                requested.set_bit(bit);
+            }
+        }
+        outcome.applied = before ^ *self;
+        outcome.unchanged = requested - outcome.applied;
+        outcome
+    }
+
+    fn check_bit(bit: usize) -> Result<(), BitError> {
+        if bit >= N {
+            return Err(BitError::OutOfRange { bit, width: N });
+        }
+        Ok(())
+    }
+
+    /// Collects the requested positions, or every out of range one if there is any.
+    #[cfg(feature = "alloc")]
+    fn check_bits<I>(bits: I) -> Result<Self, BitError>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        let mut requested = Self::new();
+        let mut rejected = alloc::vec::Vec::new();
+        for bit in bits {
+            let bit = bit.into();
+            if !requested.set_bit(bit) {
+                rejected.push(bit);
+            }
+        }
+        if !rejected.is_empty() {
+            return Err(BitError::BatchOutOfRange {
+                bits: rejected,
+                width: N,
+            });
+        }
+        Ok(requested)
+    }
+
+    /// Returns the mask of the positions of `range`, spread over the words.
+    fn range_mask(range: impl RangeBounds<usize>) -> Result<Self, BitError> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => N,
+        };
+        if start > end || end > N {
+            return Err(BitError::InvalidRange {
+                start,
+                end,
+                width: N,
+            });
+        }
+        let mut mask = Self::new();
+        for (index, word) in mask.words.iter_mut().enumerate() {
+            let low = index * WORD_BITS;
+            let word_start = start.clamp(low, low + WORD_BITS) - low;
+            let word_end = end.clamp(low, low + WORD_BITS) - low;
+            if word_start < word_end {
+                *word = u64::MAX >> (WORD_BITS - (word_end - word_start)) << word_start;
+            }
+        }
+        Ok(mask)
+    }
+
+    fn zip_words(&self, other: &Self, operation: impl Fn(u64, u64) -> u64) -> Self {
+        let mut words = self.words;
+        for (word, &other) in words.iter_mut().zip(&other.words) {
+            *word = operation(*word, other);
+        }
+        BitArray { words }
+    }
+
+    /// Clears the unused high bits of the last word.
+    fn clear_tail(&mut self) {
+        let tail = N % WORD_BITS;
+        if tail != 0 {
+            self.words[W - 1] &= (1 << tail) - 1;
+        }
+    }
+}
+
+impl<const N: usize, const W: usize> Default for BitArray<N, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! _impl_set_operator {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $set_method:ident) => {
+        impl<const N: usize, const W: usize> $op for BitArray<N, W> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                self.$set_method(&rhs)
+            }
+        }
+
+        impl<const N: usize, const W: usize> $op_assign for BitArray<N, W> {
+            fn $method_assign(&mut self, rhs: Self) {
+                *self = self.$set_method(&rhs);
+            }
+        }
+    };
+}
+
+_impl_set_operator!(BitOr, bitor, BitOrAssign, bitor_assign, union);
+_impl_set_operator!(BitAnd, bitand, BitAndAssign, bitand_assign, intersection);
+_impl_set_operator!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference
+);
+_impl_set_operator!(Sub, sub, SubAssign, sub_assign, difference);
+
+impl<const N: usize, const W: usize> Not for BitArray<N, W> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl<const N: usize, const W: usize> IntoIterator for &BitArray<N, W> {
+    type Item = usize;
+    type IntoIter = ArrayIterOnes<W>;
+
+    /// Iterates over the positions of the set bits, see [`BitArray::iter_ones`].
+    fn into_iter(self) -> ArrayIterOnes<W> {
+        self.iter_ones()
+    }
+}
+
+impl<const N: usize, const W: usize> FromIterator<usize> for BitArray<N, W> {
+    /// Builds a `BitArray` with the bits at the given positions set.
+    ///
+    /// Out of range positions are skipped, as with [`BitArray::set_bit`].
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut bits = Self::new();
+        bits.extend(iter);
+        bits
+    }
+}
+
+impl<const N: usize, const W: usize> Extend<usize> for BitArray<N, W> {
+    /// Sets the bits at the given positions, skipping out of range positions as with
+    /// [`BitArray::set_bit`].
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for bit in iter {
+            self.set_bit(bit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Array130 = BitArray<130, { words_for(130) }>;
+
+    #[test]
+    fn set_bit_across_words() {
+        let mut bits = Array130::new();
+
+        for bit in [0, 63, 64, 127, 128, 129] {
+            assert!(bits.set_bit(bit));
+            assert!(bits.is_bit_on(bit));
+        }
+        assert!(!bits.set_bit(130));
+        assert!(!bits.is_bit_on(130));
+        assert_eq!(bits.get_value(), [1 | 1 << 63, 1 | 1 << 63, 0b11]);
+
+        assert!(bits.clear_bit(64));
+        assert!(!bits.clear_bit(130));
+        assert_eq!(bits.get_value(), [1 | 1 << 63, 1 << 63, 0b11]);
+
+        assert!(bits.toggle_bit(64));
+        assert!(bits.toggle_bit(63));
+        assert!(!bits.toggle_bit(130));
+        assert_eq!(bits.get_value(), [1, 1 | 1 << 63, 0b11]);
+    }
+
+    #[test]
+    fn batch_operations() {
+        let mut bits = Array130::new();
+
+        let outcome = bits.set_bits([1_usize, 63, 64, 129, 130, 64]);
+        assert!(outcome.applied().iter_ones().eq([1, 63, 64, 129]));
+        assert_eq!(outcome.unchanged().count_ones(), 0);
+        assert_eq!(outcome.rejected_count(), 1);
+        assert_eq!(outcome.first_rejected(), Some(130));
+        assert!(bits
+            .are_bits_on([63_usize, 64, 65, 500])
+            .eq([true, true, false, false]));
+
+        let outcome = bits.clear_bits([63_usize, 65, 129]);
+        assert!(outcome.applied().iter_ones().eq([63, 129]));
+        assert!(outcome.unchanged().iter_ones().eq([65]));
+        assert!(outcome.is_fully_applied());
+
+        let outcome = bits.toggle_bits([0_usize, 64, 127, 0]);
+        assert!(outcome.applied().iter_ones().eq([64, 127]));
+        assert!(outcome.unchanged().iter_ones().eq([0]));
+        assert!(bits.iter_ones().eq([1, 127]));
+    }
+
+    #[test]
+    fn fallible_operations() {
+        let mut bits = Array130::new();
+
+        assert_eq!(bits.try_set_bit(129), Ok(()));
+        assert_eq!(bits.try_is_bit_on(129), Ok(true));
+        assert_eq!(
+            bits.try_clear_bit(130),
+            Err(BitError::OutOfRange {
+                bit: 130,
+                width: 130
+            })
+        );
+        assert!(bits.strict_is_bit_on(129));
+        bits.strict_clear_bit(129);
+        bits.strict_set_bit(64);
+        assert!(bits.iter_ones().eq([64]));
+    }
+
+    #[test]
+    #[should_panic(expected = "bit 200 is out of range for a width of 130 bits")]
+    fn strict_set_bit_panics() {
+        Array130::new().strict_set_bit(200);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_batch_operations() {
+        let mut bits = Array130::new();
+
+        assert_eq!(
+            bits.try_set_bits([1_usize, 200, 64, 130]),
+            Err(BitError::BatchOutOfRange {
+                bits: vec![200, 130],
+                width: 130
+            })
+        );
+        assert_eq!(bits.count_ones(), 0);
+        bits.strict_set_bits([1_usize, 64, 129]);
+        assert!(bits.try_clear_bits([64_usize, 129]).is_ok());
+        assert!(bits.iter_ones().eq([1]));
+        bits.strict_clear_bits([1_usize]);
+        assert_eq!(bits, Array130::new());
+    }
+
+    #[test]
+    fn ranges_across_words() {
+        let mut bits = Array130::new();
+
+        bits.set_range(60..=128).unwrap();
+        assert_eq!(bits.get_value(), [0xF << 60, u64::MAX, 1]);
+        assert_eq!(bits.count_range(..), Ok(69));
+        assert_eq!(bits.count_range(64..128), Ok(64));
+
+        bits.clear_range(62..66).unwrap();
+        assert_eq!(bits.get_value(), [0b11 << 60, !0b11, 1]);
+        bits.toggle_range(128..).unwrap();
+        assert_eq!(bits.get_value(), [0b11 << 60, !0b11, 0b10]);
+
+        assert_eq!(bits.first_set_in_range(62..), Ok(Some(66)));
+        assert_eq!(bits.last_set_in_range(..129), Ok(Some(127)));
+        assert_eq!(bits.last_set_in_range(..60), Ok(None));
+        assert_eq!(bits.any_in_range(62..66), Ok(false));
+        assert_eq!(bits.all_in_range(66..128), Ok(true));
+        assert_eq!(bits.all_in_range(64..64), Ok(true));
+
+        assert_eq!(
+            bits.set_range(..=130),
+            Err(BitError::InvalidRange {
+                start: 0,
+                end: 131,
+                width: 130
+            })
+        );
+        assert!(bits
+            .clear_range((Bound::Excluded(usize::MAX), Bound::Unbounded))
+            .is_err());
+        assert!(bits.toggle_range(..=usize::MAX).is_err());
+        assert_eq!(bits.count_ones(), 65);
+    }
+
+    #[test]
+    fn iterate_across_words() {
+        let bits: Array130 = [0, 63, 64, 128, 129, 400].into_iter().collect();
+
+        assert!(bits.iter_ones().eq([0, 63, 64, 128, 129]));
+        assert!(bits.iter_ones().rev().eq([129, 128, 64, 63, 0]));
+        assert_eq!(bits.iter_zeros().len(), 125);
+        assert_eq!(bits.iter_zeros().next(), Some(1));
+        assert_eq!(bits.iter_zeros().next_back(), Some(127));
+
+        let mut ones = bits.iter_ones();
+        assert_eq!(ones.next(), Some(0));
+        assert_eq!(ones.next_back(), Some(129));
+        assert_eq!(ones.len(), 3);
+        assert_eq!(ones.next_back(), Some(128));
+        assert_eq!(ones.next_back(), Some(64));
+        assert_eq!(ones.next(), Some(63));
+        assert_eq!(ones.next(), None);
+        assert_eq!(ones.next_back(), None);
+
+        let positions: Vec<usize> = (&bits).into_iter().collect();
+        assert_eq!(positions, vec![0, 63, 64, 128, 129]);
+        assert_eq!(bits.get_all_bits().len(), 130);
+        assert_eq!(bits.get_all_bits().rev().position(|on| on), Some(0));
+        assert_eq!(bits.get_all_bits().filter(|&on| on).count(), 5);
+    }
+
+    #[test]
+    fn set_algebra_across_words() {
+        let a: Array130 = [1, 63, 64, 129].into_iter().collect();
+        let b: Array130 = [63, 64, 100].into_iter().collect();
+
+        assert!((a | b).iter_ones().eq([1, 63, 64, 100, 129]));
+        assert!((a & b).iter_ones().eq([63, 64]));
+        assert!((a ^ b).iter_ones().eq([1, 100, 129]));
+        assert!((a - b).iter_ones().eq([1, 129]));
+        assert_eq!((!a).count_ones(), 126);
+        assert_eq!((!a).get_value()[2], 0b01);
+        assert_eq!(!!a, a);
+
+        let mut c = a;
+        c |= b;
+        c &= a;
+        assert_eq!(c, a);
+        c ^= b;
+        c -= b;
+        assert!(c.iter_ones().eq([1, 129]));
+
+        assert!((a & b).is_subset(&a));
+        assert!(a.is_superset(&(a & b)));
+        assert!(!a.is_subset(&b));
+        assert!((a - b).is_disjoint(&b));
+        assert!(a.contains_all([1_usize, 129]));
+        assert!(!a.contains_all([1_usize, 130]));
+        assert!(a.contains_any([2_usize, 64]));
+        assert!(!a.contains_any(130..200_usize));
+
+        let mut inverted = a;
+        inverted.invert();
+        assert_eq!(inverted, !a);
+        assert_eq!(inverted.count_zeros(), 4);
+    }
+
+    #[test]
+    fn masks_across_words() {
+        let mut mask = Array130::new();
+        mask.set_range(60..70).unwrap();
+        let mut bits: Array130 = [0, 62, 65, 129].into_iter().collect();
+
+        bits.toggle_mask(mask);
+        assert_eq!(bits.count_range(60..70), Ok(8));
+        assert!(bits.masked_eq(mask, !Array130::new() - [62, 65].into_iter().collect()));
+        bits.clear_mask(mask);
+        assert!(bits.iter_ones().eq([0, 129]));
+        bits.apply_mask(mask);
+        assert_eq!(bits.count_ones(), 12);
+        assert!(bits.masked_eq(mask, mask));
+        assert!(!bits.masked_eq(mask, Array130::new()));
+    }
+
+    #[test]
+    fn set_all_flags_keeps_tail_clear() {
+        let mut bits: BitArray<70, { words_for(70) }> = BitArray::default();
+        bits.set_all_flags();
+        assert_eq!(bits.get_value(), [u64::MAX, 0b11_1111]);
+        assert!(bits.get_all_bits().all(|on| on));
+        assert_eq!(bits.iter_zeros().next(), None);
+
+        let copy = bits;
+        bits.clear_all_bits();
+        assert_eq!(bits.get_value(), [0, 0]);
+        assert_ne!(copy, bits);
+    }
+}
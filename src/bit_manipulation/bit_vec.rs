@@ -0,0 +1,483 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::bit_manipulation::words::{word_bit, WORD_BITS};
+use crate::bit_manipulation::{BatchOutcome, Bits, WordBitsOn, WordView};
+use crate::private;
+
+/// A growable, heap-backed sequence of bits addressed by `usize` positions.
+///
+/// `BitVec` offers the same surface as [`Bits`] for option sets that do not fit in a `u128`.
+/// Bits are stored in `u64` words; any bit at or beyond `len` is always kept cleared, so two
+/// vectors with the same length and the same activated bits always compare equal.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// Creates a new, empty `BitVec`.
+    ///
+    /// The returned vector has a length of zero and does not allocate until a bit is set or
+    /// the vector is resized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitVec;
+    ///
+    /// let bits = BitVec::new();
+    /// assert!(bits.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        BitVec {
+            words: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Creates a new `BitVec` holding `len` bits, all of them initially set to 0.
+    ///
+    /// # Parameters
+    ///
+    /// - `len`: The number of addressable bits of the new vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitVec;
+    ///
+    /// let bits = BitVec::with_len(200);
+    /// assert_eq!(bits.len(), 200);
+    /// assert!(!bits.is_bit_on(199));
+    /// ```
+    pub fn with_len(len: usize) -> Self {
+        BitVec {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    /// Returns the number of addressable bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Resizes the vector so that it holds exactly `new_len` bits.
+    ///
+    /// Growing the vector appends bits set to 0. Shrinking it drops every bit at or beyond
+    /// `new_len`, so growing it again later brings those positions back cleared.
+    ///
+    /// # Parameters
+    ///
+    /// - `new_len`: The new number of addressable bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitVec;
+    ///
+    /// let mut bits = BitVec::with_len(8);
+    /// bits.set_bit(7);
+    /// bits.resize(4);
+    /// bits.resize(8);
+    /// assert!(!bits.is_bit_on(7));
+    /// ```
+    pub fn resize(&mut self, new_len: usize) {
+        self.words.resize(new_len.div_ceil(WORD_BITS), 0);
+        self.len = new_len;
+        let tail = new_len % WORD_BITS;
+        if tail != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1u64 << tail) - 1;
+            }
+        }
+    }
+
+    /// Checks if a specific bit is set (activated) within the vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `bit`: The position of the bit to check.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the specified bit is set (activated).
+    /// - `false` if the provided bit position is out of range (greater than or equal to `len`)
+    ///   or if the bit is not set.
+    pub fn is_bit_on(&self, bit: usize) -> bool {
+        if bit >= self.len {
+            return false;
+        }
+        word_bit(&self.words, bit)
+    }
+
+    /// Checks if specific bits are set (activated) within the vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to check.
+    ///
+    /// # Returns
+    ///
+    /// A lazy iterator yielding, for each requested position in order, `true` if the bit is set
+    /// and `false` otherwise. Out of range positions are reported as not set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitVec;
+    ///
+    /// let mut bits = BitVec::new();
+    /// bits.set_bit(100);
+    /// assert!(bits.are_bits_on([100_usize, 3, 5000]).eq([true, false, false]));
+    /// ```
+    pub fn are_bits_on<I>(&self, bits: I) -> WordBitsOn<'_, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        WordBitsOn::new(&self.words, self.len, bits.into_iter())
+    }
+
+    /// Sets a specific bit, growing the vector when the position is out of range.
+    ///
+    /// Unlike [`Bits::set_bit`], setting a bit at or beyond `len` first resizes the vector to
+    /// `bit + 1` bits. The memory for the new words is reserved with
+    /// [`Vec::try_reserve_exact`], so a position too large to be allocated, such as
+    /// `usize::MAX - 1`, is rejected instead of aborting the process. `usize::MAX` is always
+    /// rejected, as a vector of `usize::MAX + 1` bits cannot be described.
+    ///
+    /// # Parameters
+    ///
+    /// - `bit`: The position of the bit to set.
+    ///
+    /// # Returns
+    ///
+    /// - `true` once the specified bit has been set.
+    /// - `false` if the vector could not grow to hold `bit`, in which case it is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitVec;
+    ///
+    /// let mut bits = BitVec::new();
+    /// assert!(bits.set_bit(300));
+    /// assert_eq!(bits.len(), 301);
+    /// assert!(bits.is_bit_on(300));
+    /// assert!(!bits.set_bit(usize::MAX));
+    /// ```
+    pub fn set_bit(&mut self, bit: usize) -> bool {
+        if bit >= self.len {
+            let Some(new_len) = bit.checked_add(1) else {
+                return false;
+            };
+            let additional = new_len.div_ceil(WORD_BITS) - self.words.len();
+            if self.words.try_reserve_exact(additional).is_err() {
+                return false;
+            }
+            self.resize(new_len);
+        }
+        self.words[bit / WORD_BITS] |= 1 << (bit % WORD_BITS);
+        true
+    }
+
+    /// Sets multiple bits, growing the vector as needed.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to set.
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchOutcome`] holding the positions turned on by this call, the positions that
+    /// were already set, and the positions rejected by [`BitVec::set_bit`] because the vector
+    /// could not grow to hold them. The applied and unchanged sets have the final length of
+    /// the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitVec;
+    ///
+    /// let mut bits = BitVec::new();
+    /// bits.set_bit(3);
+    /// let outcome = bits.set_bits([3_usize, 70, 70, 5, usize::MAX]);
+    /// assert!(outcome.applied().are_bits_on([5_usize, 70, 3]).eq([true, true, false]));
+    /// assert!(outcome.unchanged().is_bit_on(3));
+    /// assert_eq!(outcome.first_rejected(), Some(usize::MAX));
+    /// assert_eq!(bits.len(), 71);
+    /// ```
+    pub fn set_bits<I>(&mut self, bits: I) -> BatchOutcome<BitVec>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        let mut outcome = BatchOutcome::new(BitVec::new(), BitVec::new());
+        for bit in bits {
+            let bit = bit.into();
+            if outcome.applied.is_bit_on(bit) || outcome.unchanged.is_bit_on(bit) {
+                continue;
+            }
+            if self.is_bit_on(bit) {
+                outcome.unchanged.set_bit(bit);
+            } else if self.set_bit(bit) {
+                outcome.applied.set_bit(bit);
+            } else {
+                outcome.reject(bit);
+            }
+        }
+        outcome.applied.resize(self.len);
+        outcome.unchanged.resize(self.len);
+        outcome
+    }
+
+    /// Clears a specific bit, setting it to 0.
+    ///
+    /// The vector never grows when clearing: a position at or beyond `len` is already
+    /// considered cleared.
+    ///
+    /// # Parameters
+    ///
+    /// - `bit`: The position of the bit to clear.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the specified bit was successfully cleared.
+    /// - `false` if the provided bit position is out of range (greater than or equal to `len`).
+    pub fn clear_bit(&mut self, bit: usize) -> bool {
+        if bit >= self.len {
+            return false;
+        }
+        self.words[bit / WORD_BITS] &= !(1 << (bit % WORD_BITS));
+        true
+    }
+
+    /// Clears multiple bits, skipping the positions that are out of range.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to clear.
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchOutcome`] holding the positions turned off by this call, the positions that
+    /// were already cleared, and the positions rejected because they are out of range
+    /// (greater than or equal to `len`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::BitVec;
+    ///
+    /// let mut bits = BitVec::with_len(100);
+    /// bits.set_bit(80);
+    /// let outcome = bits.clear_bits([80_usize, 2, 100]);
+    /// assert!(outcome.applied().is_bit_on(80));
+    /// assert!(outcome.unchanged().is_bit_on(2));
+    /// assert_eq!(outcome.rejected_count(), 1);
+    /// ```
+    pub fn clear_bits<I>(&mut self, bits: I) -> BatchOutcome<BitVec>
+    where
+        I: IntoIterator,
+        I::Item: Into<usize>,
+    {
+        let mut outcome = BatchOutcome::new(BitVec::with_len(self.len), BitVec::with_len(self.len));
+        for bit in bits {
+            let bit = bit.into();
+            if bit >= self.len {
+                outcome.reject(bit);
+            } else if outcome.applied.is_bit_on(bit) || outcome.unchanged.is_bit_on(bit) {
+                continue;
+            } else if self.is_bit_on(bit) {
+                self.clear_bit(bit);
+                outcome.applied.set_bit(bit);
+            } else {
+                outcome.unchanged.set_bit(bit);
+            }
+        }
+        outcome
+    }
+
+    /// Clears all bits, setting them to 0. The length of the vector is left unchanged.
+    pub fn clear_all_bits(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Sets all `len` bits to 1.
+    pub fn set_all_flags(&mut self) {
+        self.words.fill(u64::MAX);
+        self.resize(self.len);
+    }
+
+    /// Returns a lazy view over the state of every bit in the vector, from position 0 to
+    /// `len - 1`.
+    pub fn get_all_bits(&self) -> WordView<'_> {
+        WordView::new(&self.words, self.len)
+    }
+
+    /// Returns the underlying `u64` words, least significant word first.
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+}
+
+impl<T> From<Bits<T>> for BitVec
+where
+    T: private::Uint,
+{
+    /// Converts a `Bits<T>` into a `BitVec` whose length is the bit width of `T`.
+    fn from(bits: Bits<T>) -> Self {
+        let mut bit_vec = BitVec::with_len(T::in_memory_size() as usize);
+        for bit in 0..T::in_memory_size() {
+            if bits.is_bit_on(bit) {
+                bit_vec.set_bit(bit as usize);
+            }
+        }
+        bit_vec
+    }
+}
+
+impl<T> TryFrom<BitVec> for Bits<T>
+where
+    T: private::Uint,
+{
+    type Error = BitVec;
+
+    /// Converts a `BitVec` into a `Bits<T>`.
+    ///
+    /// The conversion succeeds only when the length of the vector fits the bit width of `T`,
+    /// otherwise the vector is handed back unchanged as the error.
+    fn try_from(bit_vec: BitVec) -> Result<Self, Self::Error> {
+        if bit_vec.len() > T::in_memory_size() as usize {
+            return Err(bit_vec);
+        }
+        let mut bits = Bits::new();
+        for bit in 0..bit_vec.len() {
+            if bit_vec.is_bit_on(bit) {
+                bits.set_bit(bit as u8);
+            }
+        }
+        Ok(bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_bit_grows() {
+        let mut bits = BitVec::new();
+
+        assert!(bits.set_bit(0));
+        assert!(bits.set_bit(64));
+        assert!(bits.set_bit(200));
+
+        assert_eq!(bits.len(), 201);
+        assert_eq!(bits.as_words().len(), 4);
+        assert!(bits.is_bit_on(0));
+        assert!(bits.is_bit_on(64));
+        assert!(bits.is_bit_on(200));
+        assert!(!bits.is_bit_on(63));
+        assert!(!bits.is_bit_on(1000));
+
+        assert!(!bits.set_bit(usize::MAX));
+        assert_eq!(bits.len(), 201);
+
+        let outcome = bits.set_bits([0, 1, usize::MAX, 1]);
+        assert!(outcome
+            .applied()
+            .get_all_bits()
+            .eq((0..201).map(|bit| bit == 1)));
+        assert!(outcome
+            .unchanged()
+            .are_bits_on([0_usize, 1])
+            .eq([true, false]));
+        assert_eq!(outcome.rejected_count(), 1);
+        assert_eq!(outcome.first_rejected(), Some(usize::MAX));
+        assert!(bits
+            .are_bits_on([1_usize, 2, 5000])
+            .eq([true, false, false]));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn set_bit_rejects_unallocatable_positions() {
+        let mut bits = BitVec::with_len(10);
+        bits.set_bit(3);
+
+        assert!(!bits.set_bit(1 << 60));
+        assert!(!bits.set_bit(usize::MAX - 1));
+        assert_eq!(bits.len(), 10);
+        assert_eq!(bits.as_words(), &[0b1000]);
+
+        let outcome = bits.set_bits([usize::MAX - 1, 4, 1 << 60]);
+        assert_eq!(outcome.rejected_count(), 2);
+        assert_eq!(outcome.first_rejected(), Some(1 << 60));
+        assert!(bits.is_bit_on(4));
+    }
+
+    #[test]
+    fn clear_bits() {
+        let mut bits = BitVec::with_len(130);
+        bits.set_bits([1_usize, 65, 129]);
+
+        assert!(!bits.clear_bit(130));
+        assert_eq!(bits.len(), 130);
+
+        let outcome = bits.clear_bits([1_usize, 129, 500, 1, 0]);
+        assert!(outcome
+            .applied()
+            .are_bits_on([1_usize, 129])
+            .eq([true, true]));
+        assert_eq!(outcome.applied().get_all_bits().filter(|&on| on).count(), 2);
+        assert!(outcome.unchanged().is_bit_on(0));
+        assert_eq!(outcome.first_rejected(), Some(500));
+        assert!(bits.is_bit_on(65));
+        assert!(!bits.is_bit_on(129));
+
+        bits.clear_all_bits();
+        assert!(bits.as_words().iter().all(|&word| word == 0));
+    }
+
+    #[test]
+    fn resize_clears_truncated_bits() {
+        let mut bits = BitVec::with_len(70);
+        bits.set_all_flags();
+        assert_eq!(bits.as_words(), &[u64::MAX, 0b11_1111]);
+
+        bits.resize(66);
+        bits.resize(70);
+        assert!(bits.is_bit_on(65));
+        assert!(!bits.is_bit_on(66));
+        assert_eq!(bits, {
+            let mut expected = BitVec::with_len(70);
+            expected.set_bits(0..66_usize);
+            expected
+        });
+    }
+
+    #[test]
+    fn convert_from_and_to_bits() {
+        let mut bits: Bits<u16> = Bits::new();
+        bits.set_bits([0, 9, 15]);
+
+        let bit_vec = BitVec::from(bits);
+        assert_eq!(bit_vec.len(), 16);
+        assert_eq!(bit_vec.as_words(), &[0b1000_0010_0000_0001]);
+
+        let bits: Bits<u16> = bit_vec.clone().try_into().unwrap();
+        assert_eq!(bits.get_value(), 0b1000_0010_0000_0001);
+
+        let narrow: Result<Bits<u8>, BitVec> = bit_vec.try_into();
+        assert_eq!(narrow.unwrap_err().len(), 16);
+    }
+}
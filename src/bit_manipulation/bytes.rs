@@ -0,0 +1,155 @@
+use crate::bit_manipulation::Bits;
+use crate::private;
+
+/// The numbering of the bits inside each byte of a serialized [`Bits`] value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// Position 0 is the least significant bit of the first byte.
+    Lsb0,
+    /// Position 0 is the most significant bit of the first byte, as in most network protocols.
+    Msb0,
+}
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Returns the bytes of the value, least significant byte first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0x1234_u16);
+    /// assert_eq!(bits.to_le_bytes(), [0x34, 0x12]);
+    /// assert_eq!(bits.to_be_bytes(), [0x12, 0x34]);
+    /// ```
+    pub fn to_le_bytes(&self) -> T::Bytes {
+        self.value.to_le_bytes()
+    }
+
+    /// Returns the bytes of the value, most significant byte first.
+    pub fn to_be_bytes(&self) -> T::Bytes {
+        let mut bytes = self.value.to_le_bytes();
+        bytes.as_mut().reverse();
+        bytes
+    }
+
+    /// Creates a new instance from bytes ordered least significant byte first.
+    pub fn from_le_bytes(bytes: T::Bytes) -> Self {
+        Bits::from_value(T::from_le_bytes(bytes))
+    }
+
+    /// Creates a new instance from bytes ordered most significant byte first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::<u32>::from_be_bytes([0, 0, 1, 2]);
+    /// assert_eq!(bits.get_value(), 0x0102);
+    /// ```
+    pub fn from_be_bytes(mut bytes: T::Bytes) -> Self {
+        bytes.as_mut().reverse();
+        Bits::from_le_bytes(bytes)
+    }
+
+    /// Serializes the value so that positions follow each other in the byte stream.
+    ///
+    /// Byte `k` holds positions `8 * k` to `8 * k + 7`, numbered inside the byte according to
+    /// `order`. With [`BitOrder::Lsb0`] this is [`Bits::to_le_bytes`]. With [`BitOrder::Msb0`],
+    /// position 0 is the most significant bit of the first byte, which is how protocols that
+    /// number bit 0 as the most significant one lay out their flags.
+    ///
+    /// # Parameters
+    ///
+    /// - `order`: The numbering of the bits inside each byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitOrder, Bits};
+    ///
+    /// let mut bits: Bits<u16> = Bits::new();
+    /// bits.set_bits([0, 9]);
+    /// assert_eq!(bits.to_bytes(BitOrder::Lsb0), [0b0000_0001, 0b0000_0010]);
+    /// assert_eq!(bits.to_bytes(BitOrder::Msb0), [0b1000_0000, 0b0100_0000]);
+    /// ```
+    pub fn to_bytes(&self, order: BitOrder) -> T::Bytes {
+        let mut bytes = self.to_le_bytes();
+        if order == BitOrder::Msb0 {
+            for byte in bytes.as_mut() {
+                *byte = byte.reverse_bits();
+            }
+        }
+        bytes
+    }
+
+    /// Deserializes a value written by [`Bits::to_bytes`] with the same `order`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitOrder, Bits};
+    ///
+    /// let bits = Bits::<u16>::from_bytes([0b1000_0000, 0b0000_0001], BitOrder::Msb0);
+    /// assert!(bits.iter_ones().eq([0, 15]));
+    /// ```
+    pub fn from_bytes(mut bytes: T::Bytes, order: BitOrder) -> Self {
+        if order == BitOrder::Msb0 {
+            for byte in bytes.as_mut() {
+                *byte = byte.reverse_bits();
+            }
+        }
+        Bits::from_le_bytes(bytes)
+    }
+}
+
+// SAFETY: `Bits<T>` is `#[repr(transparent)]` over `T`, so it is zeroable whenever `T` is.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T> bytemuck::Zeroable for Bits<T> where T: private::Uint + bytemuck::Zeroable {}
+
+// SAFETY: `Bits<T>` is `#[repr(transparent)]` over `T` and every bit pattern of `T` is a valid
+// `Bits<T>`, so it is plain old data whenever `T` is.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T> bytemuck::Pod for Bits<T> where T: private::Uint + bytemuck::Pod {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_order() {
+        let bits = Bits::from_value(0x0102_0304_i32);
+        assert_eq!(bits.to_le_bytes(), [4, 3, 2, 1]);
+        assert_eq!(bits.to_be_bytes(), [1, 2, 3, 4]);
+        assert_eq!(Bits::from_le_bytes(bits.to_le_bytes()), bits);
+        assert_eq!(Bits::from_be_bytes(bits.to_be_bytes()), bits);
+    }
+
+    #[test]
+    fn bit_order() {
+        let mut bits: Bits<u32> = Bits::new();
+        bits.set_bits([0, 3, 12, 31]);
+
+        let msb0 = bits.to_bytes(BitOrder::Msb0);
+        assert_eq!(msb0, [0b1001_0000, 0b0000_1000, 0, 0b0000_0001]);
+        assert_eq!(Bits::from_bytes(msb0, BitOrder::Msb0), bits);
+        assert_eq!(bits.to_bytes(BitOrder::Lsb0), bits.to_le_bytes());
+        assert_eq!(Bits::from_bytes(bits.to_le_bytes(), BitOrder::Lsb0), bits);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn cast_in_place() {
+        let mut words = [Bits::from_value(0x0102_u16), Bits::from_value(0x0304)];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut words);
+        bytes[0] = 0xFF;
+        assert_eq!(words[0].get_value(), 0x01FF);
+
+        let values: &[u16] = bytemuck::cast_slice(&words);
+        assert_eq!(values, &[0x01FF, 0x0304]);
+    }
+}
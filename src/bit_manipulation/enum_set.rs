@@ -0,0 +1,330 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::bit_manipulation::{Bits, IterOnes};
+use crate::private;
+
+/// A fieldless enum whose variants are mapped to bit positions.
+///
+/// This trait is usually implemented with `#[derive(BitOption)]`, available with the `derive`
+/// feature, which assigns each variant either the position given by its `#[bit(n)]` attribute
+/// or the position following the one of the previous variant, starting from 0.
+pub trait BitOption: Copy + 'static {
+    /// The narrowest unsigned integer able to hold every position.
+    type Repr: private::Uint;
+
+    /// Every variant, in declaration order.
+    const VARIANTS: &'static [Self];
+
+    /// Returns the bit position of the variant.
+    fn position(self) -> u8;
+
+    /// Returns the variant stored at `position`, if any.
+    fn from_position(position: u8) -> Option<Self>;
+}
+
+/// A set of enum variants stored as bits of a [`Bits`] value.
+///
+/// The backing integer is [`BitOption::Repr`], chosen from the positions of the variants of `E`.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::{BitOption, EnumSet};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Permission {
+///     Read,
+///     Write,
+/// }
+///
+/// impl BitOption for Permission {
+///     type Repr = u8;
+///     const VARIANTS: &'static [Self] = &[Permission::Read, Permission::Write];
+///
+///     fn position(self) -> u8 {
+///         self as u8
+///     }
+///
+///     fn from_position(position: u8) -> Option<Self> {
+///         Self::VARIANTS.get(position as usize).copied()
+///     }
+/// }
+///
+/// let mut permissions = EnumSet::new();
+/// permissions.insert(Permission::Write);
+/// assert!(permissions.contains(Permission::Write));
+/// assert!(permissions.iter().eq([Permission::Write]));
+/// ```
+pub struct EnumSet<E>
+where
+    E: BitOption,
+{
+    bits: Bits<E::Repr>,
+    marker: PhantomData<E>,
+}
+
+impl<E> EnumSet<E>
+where
+    E: BitOption,
+{
+    /// Creates a new, empty `EnumSet`.
+    pub fn new() -> Self {
+        EnumSet::from_bits(Bits::new())
+    }
+
+    /// Creates a new `EnumSet` holding every variant of `E`.
+    pub fn all() -> Self {
+        E::VARIANTS.iter().copied().collect()
+    }
+
+    /// Creates a new `EnumSet` from its underlying bits.
+    ///
+    /// Bits that do not match any variant are kept, but are never yielded by
+    /// [`EnumSet::iter`].
+    pub fn from_bits(bits: Bits<E::Repr>) -> Self {
+        EnumSet {
+            bits,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying bits.
+    pub fn bits(&self) -> Bits<E::Repr> {
+        self.bits
+    }
+
+    /// Adds a variant to the set.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the variant was not already in the set.
+    /// - `false` otherwise.
+    pub fn insert(&mut self, option: E) -> bool {
+        let inserted = !self.contains(option);
+        self.bits.set_bit(option.position());
+        inserted
+    }
+
+    /// Removes a variant from the set.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the variant was in the set.
+    /// - `false` otherwise.
+    pub fn remove(&mut self, option: E) -> bool {
+        let removed = self.contains(option);
+        self.bits.clear_bit(option.position());
+        removed
+    }
+
+    /// Returns `true` if the variant is in the set.
+    pub fn contains(&self, option: E) -> bool {
+        self.bits.is_bit_on(option.position())
+    }
+
+    /// Returns the number of variants in the set.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns `true` if the set holds no variant.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Removes every variant from the set.
+    pub fn clear(&mut self) {
+        self.bits.clear_all_bits();
+    }
+
+    /// Returns an iterator over the variants of the set, in ascending position order.
+    pub fn iter(&self) -> EnumSetIter<E> {
+        EnumSetIter {
+            positions: self.bits.iter_ones(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<E> Clone for EnumSet<E>
+where
+    E: BitOption,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for EnumSet<E> where E: BitOption {}
+
+impl<E> Default for EnumSet<E>
+where
+    E: BitOption,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> PartialEq for EnumSet<E>
+where
+    E: BitOption,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<E> Eq for EnumSet<E> where E: BitOption {}
+
+impl<E> Hash for EnumSet<E>
+where
+    E: BitOption,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<E> fmt::Debug for EnumSet<E>
+where
+    E: BitOption + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<E> FromIterator<E> for EnumSet<E>
+where
+    E: BitOption,
+{
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut set = EnumSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<E> Extend<E> for EnumSet<E>
+where
+    E: BitOption,
+{
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        for option in iter {
+            self.insert(option);
+        }
+    }
+}
+
+impl<E> IntoIterator for &EnumSet<E>
+where
+    E: BitOption,
+{
+    type Item = E;
+    type IntoIter = EnumSetIter<E>;
+
+    fn into_iter(self) -> EnumSetIter<E> {
+        self.iter()
+    }
+}
+
+/// An iterator over the variants of an [`EnumSet`].
+///
+/// This struct is created by [`EnumSet::iter`].
+pub struct EnumSetIter<E>
+where
+    E: BitOption,
+{
+    positions: IterOnes<E::Repr>,
+    marker: PhantomData<E>,
+}
+
+impl<E> Iterator for EnumSetIter<E>
+where
+    E: BitOption,
+{
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        self.positions.by_ref().find_map(E::from_position)
+    }
+}
+
+impl<E> DoubleEndedIterator for EnumSetIter<E>
+where
+    E: BitOption,
+{
+    fn next_back(&mut self) -> Option<E> {
+        while let Some(position) = self.positions.next_back() {
+            if let Some(option) = E::from_position(position) {
+                return Some(option);
+            }
+        }
+        None
+    }
+}
+
+impl<E> FusedIterator for EnumSetIter<E> where E: BitOption {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Level {
+        Low,
+        High,
+    }
+
+    impl BitOption for Level {
+        type Repr = u16;
+        const VARIANTS: &'static [Self] = &[Level::Low, Level::High];
+
+        fn position(self) -> u8 {
+            match self {
+                Level::Low => 1,
+                Level::High => 9,
+            }
+        }
+
+        fn from_position(position: u8) -> Option<Self> {
+            match position {
+                1 => Some(Level::Low),
+                9 => Some(Level::High),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn insert_remove_contains() {
+        let mut set = EnumSet::new();
+        assert!(set.is_empty());
+
+        assert!(set.insert(Level::High));
+        assert!(!set.insert(Level::High));
+        assert!(set.contains(Level::High));
+        assert!(!set.contains(Level::Low));
+        assert_eq!(set.bits().get_value(), 1 << 9);
+
+        assert!(set.remove(Level::High));
+        assert!(!set.remove(Level::High));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn iterate_variants() {
+        let set = EnumSet::<Level>::all();
+        assert_eq!(set.len(), 2);
+        assert!(set.iter().eq([Level::Low, Level::High]));
+        assert!(set.iter().rev().eq([Level::High, Level::Low]));
+        assert_eq!(format!("{set:?}"), "{Low, High}");
+
+        let unknown = EnumSet::<Level>::from_bits(Bits::from_value(1 << 9 | 1 << 4));
+        assert!(unknown.iter().eq([Level::High]));
+    }
+}
@@ -0,0 +1,124 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// The error returned by the fallible (`try_`) bit operations and by parsing.
+///
+/// Some variants only exist with the `alloc` feature, and any crate of a build may turn that
+/// feature on, so the enum is `#[non_exhaustive]`: matches outside this crate need a wildcard
+/// arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BitError {
+    /// A bit position is greater than or equal to the number of addressable bits.
+    OutOfRange {
+        /// The rejected position.
+        bit: usize,
+        /// The number of addressable bits.
+        width: usize,
+    },
+    /// One or more positions of a batch operation are greater than or equal to the number of
+    /// addressable bits. No bit is modified when a batch is rejected.
+    #[cfg(feature = "alloc")]
+    BatchOutOfRange {
+        /// Every rejected position, in the order they were given.
+        bits: Vec<usize>,
+        /// The number of addressable bits.
+        width: usize,
+    },
+    /// A bit range is reversed or ends beyond the number of addressable bits.
+    InvalidRange {
+        /// The first position of the range.
+        start: usize,
+        /// The position right after the last one of the range.
+        end: usize,
+        /// The number of addressable bits.
+        width: usize,
+    },
+    /// A value does not fit in the bit field it is written to.
+    FieldOverflow {
+        /// The number of bits of the field.
+        field_width: usize,
+    },
+    /// A character of a parsed number is not a digit of its base.
+    InvalidDigit {
+        /// The rejected character.
+        digit: char,
+        /// The byte offset of the character in the parsed text.
+        index: usize,
+    },
+    /// A parsed number holds no digit.
+    MissingDigits,
+    /// A flag expression names a flag that is not in the name table.
+    #[cfg(feature = "alloc")]
+    UnknownFlag {
+        /// The unknown name.
+        name: String,
+        /// The column of the first character of the name, starting from 1.
+        column: usize,
+    },
+    /// A flag expression holds a character that cannot appear at this place.
+    UnexpectedCharacter {
+        /// The rejected character.
+        character: char,
+        /// The column of the character, starting from 1.
+        column: usize,
+    },
+    /// A flag expression ends in the middle of an operation.
+    UnexpectedEnd {
+        /// The column right after the last character, starting from 1.
+        column: usize,
+    },
+    /// A flag expression nests parentheses and `!` deeper than
+    /// [`FlagTable::MAX_DEPTH`](crate::bit_manipulation::FlagTable::MAX_DEPTH).
+    NestingTooDeep {
+        /// The column of the `(` or `!` that exceeds the limit, starting from 1.
+        column: usize,
+    },
+}
+
+impl fmt::Display for BitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitError::OutOfRange { bit, width } => {
+                write!(f, "bit {bit} is out of range for a width of {width} bits")
+            }
+            #[cfg(feature = "alloc")]
+            BitError::BatchOutOfRange { bits, width } => {
+                write!(
+                    f,
+                    "bits {bits:?} are out of range for a width of {width} bits"
+                )
+            }
+            BitError::InvalidRange { start, end, width } => {
+                write!(
+                    f,
+                    "bit range {start}..{end} is invalid for a width of {width} bits"
+                )
+            }
+            BitError::FieldOverflow { field_width } => {
+                write!(f, "value does not fit in a field of {field_width} bits")
+            }
+            BitError::InvalidDigit { digit, index } => {
+                write!(f, "invalid digit {digit:?} at index {index}")
+            }
+            BitError::MissingDigits => f.write_str("number has no digits"),
+            #[cfg(feature = "alloc")]
+            BitError::UnknownFlag { name, column } => {
+                write!(f, "unknown flag `{name}` at column {column}")
+            }
+            BitError::UnexpectedCharacter { character, column } => {
+                write!(f, "unexpected character {character:?} at column {column}")
+            }
+            BitError::UnexpectedEnd { column } => {
+                write!(f, "unexpected end of expression at column {column}")
+            }
+            BitError::NestingTooDeep { column } => {
+                write!(f, "expression nested too deeply at column {column}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for BitError {}
@@ -0,0 +1,248 @@
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::bit_manipulation::{Bits, Mask};
+use crate::private;
+
+/// An event group: a [`Bits`] value that threads can block on until some of its bits are set.
+///
+/// Threads wait with [`EventBits::wait_all`] or [`EventBits::wait_any`] for the bits of a mask,
+/// while other threads publish events with [`EventBits::set_bits`]. The `_and_clear` variants of
+/// the waits clear the bits of the mask when the wait succeeds, in the same critical section, so
+/// that only one waiter consumes a given event.
+///
+/// The value is guarded by a [`Mutex`] and waiters are woken through a [`Condvar`], so this type
+/// needs the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bit_manipulation::bit_manipulation::{EventBits, Mask};
+///
+/// const RX_DONE: Mask<u8> = Mask::from_value(0b01);
+/// const TX_DONE: Mask<u8> = Mask::from_value(0b10);
+///
+/// let events = EventBits::<u8>::default();
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| events.set_bits(RX_DONE));
+///     scope.spawn(|| events.set_bits(TX_DONE));
+///
+///     let bits = events.wait_all_and_clear(RX_DONE | TX_DONE, Duration::MAX);
+///     assert_eq!(bits.map(|bits| bits.get_value()), Ok(0b11));
+/// });
+/// assert_eq!(events.get().get_value(), 0);
+/// ```
+pub struct EventBits<T>
+where
+    T: private::Uint,
+{
+    bits: Mutex<Bits<T>>,
+    changed: Condvar,
+}
+
+impl<T> EventBits<T>
+where
+    T: private::Uint,
+{
+    /// Creates an event group holding `bits`.
+    pub fn new(bits: Bits<T>) -> Self {
+        EventBits {
+            bits: Mutex::new(bits),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Returns the current bits.
+    pub fn get(&self) -> Bits<T> {
+        *self.lock()
+    }
+
+    /// Turns on every bit selected by `mask` and wakes the waiting threads.
+    ///
+    /// # Returns
+    ///
+    /// The bits before the call.
+    pub fn set_bits(&self, mask: Mask<T>) -> Bits<T> {
+        let mut bits = self.lock();
+        let previous = *bits;
+        bits.apply_mask(mask);
+        self.changed.notify_all();
+        previous
+    }
+
+    /// Turns off every bit selected by `mask`.
+    ///
+    /// # Returns
+    ///
+    /// The bits before the call.
+    pub fn clear_bits(&self, mask: Mask<T>) -> Bits<T> {
+        let mut bits = self.lock();
+        let previous = *bits;
+        bits.clear_mask(mask);
+        previous
+    }
+
+    /// Blocks until every bit selected by `mask` is set, or until `timeout` elapses.
+    ///
+    /// A `timeout` too large to be represented as a deadline, such as [`Duration::MAX`], waits
+    /// forever. An empty mask is satisfied right away.
+    ///
+    /// # Parameters
+    ///
+    /// - `mask`: The bits to wait for.
+    /// - `timeout`: The longest time to block for.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(bits)` with the bits that satisfied the wait.
+    /// - `Err(bits)` with the current bits if the timeout elapsed first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bit_manipulation::bit_manipulation::{Bits, EventBits, Mask};
+    ///
+    /// let events = EventBits::new(Bits::from_value(0b01_u8));
+    /// let ready = Mask::from_value(0b11);
+    /// let result = events.wait_all(ready, Duration::from_millis(10));
+    /// assert_eq!(result.map_err(|bits| bits.get_value()), Err(0b01));
+    /// ```
+    pub fn wait_all(&self, mask: Mask<T>, timeout: Duration) -> Result<Bits<T>, Bits<T>> {
+        self.wait(mask, timeout, true, false)
+    }
+
+    /// Blocks until at least one bit selected by `mask` is set, or until `timeout` elapses.
+    ///
+    /// An empty mask is never satisfied, so the wait always times out. See
+    /// [`EventBits::wait_all`] for the parameters and the returned value.
+    pub fn wait_any(&self, mask: Mask<T>, timeout: Duration) -> Result<Bits<T>, Bits<T>> {
+        self.wait(mask, timeout, false, false)
+    }
+
+    /// Like [`EventBits::wait_all`], then clears the bits selected by `mask` if the wait
+    /// succeeded.
+    ///
+    /// The returned bits are those before clearing.
+    pub fn wait_all_and_clear(&self, mask: Mask<T>, timeout: Duration) -> Result<Bits<T>, Bits<T>> {
+        self.wait(mask, timeout, true, true)
+    }
+
+    /// Like [`EventBits::wait_any`], then clears the bits selected by `mask` if the wait
+    /// succeeded.
+    ///
+    /// The returned bits are those before clearing.
+    pub fn wait_any_and_clear(&self, mask: Mask<T>, timeout: Duration) -> Result<Bits<T>, Bits<T>> {
+        self.wait(mask, timeout, false, true)
+    }
+
+    fn wait(
+        &self,
+        mask: Mask<T>,
+        timeout: Duration,
+        all: bool,
+        clear_on_exit: bool,
+    ) -> Result<Bits<T>, Bits<T>> {
+        let is_ready = |bits: &Bits<T>| {
+            if all {
+                bits.masked_eq(mask, mask.value())
+            } else {
+                !bits.masked_eq(mask, T::ZERO)
+            }
+        };
+        let deadline = Instant::now().checked_add(timeout);
+
+        let mut bits = self.lock();
+        while !is_ready(&bits) {
+            bits = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(*bits);
+                    }
+                    self.changed
+                        .wait_timeout(bits, remaining)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .changed
+                    .wait(bits)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+
+        let satisfied = *bits;
+        if clear_on_exit {
+            bits.clear_mask(mask);
+        }
+        Ok(satisfied)
+    }
+
+    /// Locks the bits, ignoring poisoning: no operation can panic while holding the lock, so the
+    /// bits are always consistent.
+    fn lock(&self) -> MutexGuard<'_, Bits<T>> {
+        self.bits.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Default for EventBits<T>
+where
+    T: private::Uint,
+{
+    fn default() -> Self {
+        EventBits::new(Bits::new())
+    }
+}
+
+impl<T> fmt::Debug for EventBits<T>
+where
+    T: private::Uint + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EventBits").field(&self.get()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_for_events() {
+        let events = EventBits::<u16>::default();
+        let low = Mask::from_value(0x00FF);
+        std::thread::scope(|scope| {
+            let waiter = scope.spawn(|| events.wait_any_and_clear(low, Duration::MAX));
+            events.set_bits(Mask::from_value(0x0100));
+            events.set_bits(Mask::from_value(0x0004));
+            assert_eq!(waiter.join().unwrap().unwrap().get_value() & 0x00FF, 0x0004);
+        });
+        assert_eq!(events.get().get_value(), 0x0100);
+
+        events.set_bits(Mask::from_value(0x0003));
+        assert_eq!(
+            events.wait_all(Mask::from_value(0x0101), Duration::ZERO),
+            Ok(Bits::from_value(0x0103))
+        );
+        assert_eq!(events.clear_bits(low).get_value(), 0x0103);
+    }
+
+    #[test]
+    fn time_out() {
+        let events = EventBits::new(Bits::from_value(0b10_u8));
+        let start = Instant::now();
+        let result = events.wait_all_and_clear(Mask::from_value(0b11), Duration::from_millis(20));
+        assert_eq!(result, Err(Bits::from_value(0b10)));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert_eq!(
+            events.wait_any(Mask::from_value(0), Duration::ZERO),
+            Err(Bits::from_value(0b10))
+        );
+        assert_eq!(events.get().get_value(), 0b10);
+    }
+}
@@ -0,0 +1,171 @@
+use core::ops::Range;
+
+use crate::bit_manipulation::{BitError, Bits};
+use crate::private;
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Reads the small integer packed in a range of bits.
+    ///
+    /// The bits of `range` are shifted down so that `range.start` becomes bit 0 of the result.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The positions of the field, from `range.start` included to `range.end` excluded.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(value)` holding the value of the field.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// // A 3-bit priority stored in bits 4..7.
+    /// let bits = Bits::from_value(0b0101_0000_u8);
+    /// assert_eq!(bits.get_field(4..7), Ok(0b101));
+    /// ```
+    pub fn get_field(&self, range: Range<u8>) -> Result<T, BitError> {
+        let mask = Self::field_mask(&range)?;
+        if range.is_empty() {
+            return Ok(T::ZERO);
+        }
+        Ok((self.value >> range.start) & mask)
+    }
+
+    /// Writes a small integer in a range of bits, leaving the other bits unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The positions of the field, from `range.start` included to `range.end` excluded.
+    /// - `value`: The value to write, which must fit in `range.len()` bits.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the field was successfully written.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    /// - `Err(BitError::FieldOverflow)` if `value` does not fit in the field.
+    ///
+    /// The stored value is left unchanged when an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitError, Bits};
+    ///
+    /// let mut bits = Bits::from_value(0b1000_0001_u8);
+    /// bits.set_field(4..7, 0b011).unwrap();
+    /// assert_eq!(bits.get_value(), 0b1011_0001);
+    /// assert_eq!(bits.set_field(4..7, 8), Err(BitError::FieldOverflow { field_width: 3 }));
+    /// ```
+    pub fn set_field(&mut self, range: Range<u8>, value: T) -> Result<(), BitError> {
+        let mask = Self::field_mask(&range)?;
+        if value & !mask != T::ZERO {
+            return Err(BitError::FieldOverflow {
+                field_width: range.len(),
+            });
+        }
+        if !range.is_empty() {
+            self.value = (self.value & !(mask << range.start)) | (value << range.start);
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of `self` with a small integer written in a range of bits.
+    ///
+    /// This is the builder form of [`Bits::set_field`], handy to pack several fields in a row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitError, Bits};
+    ///
+    /// let header = Bits::<u16>::new()
+    ///     .with_field(0..3, 2)?
+    ///     .with_field(3..8, 17)?
+    ///     .with_field(8..16, 255)?;
+    /// assert_eq!(header.get_value(), 0b1111_1111_1000_1010);
+    /// # Ok::<(), BitError>(())
+    /// ```
+    pub fn with_field(mut self, range: Range<u8>, value: T) -> Result<Self, BitError> {
+        self.set_field(range, value)?;
+        Ok(self)
+    }
+
+    /// Returns the mask of a field of `range.len()` bits, not yet shifted to `range.start`.
+    pub(super) fn field_mask(range: &Range<u8>) -> Result<T, BitError> {
+        let width = T::in_memory_size();
+        if range.start > range.end || range.end > width {
+            return Err(BitError::InvalidRange {
+                start: range.start as usize,
+                end: range.end as usize,
+                width: width as usize,
+            });
+        }
+        if range.is_empty() {
+            return Ok(T::ZERO);
+        }
+        if range.len() == width as usize {
+            return Ok(T::ONES);
+        }
+        // Built with a left shift, which unlike a right shift does not copy the sign bit of
+        // signed integers.
+        Ok(!(T::ONES << range.len() as u8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_and_set_field() {
+        let mut bits: Bits<u32> = Bits::new();
+
+        bits.set_field(4..7, 0b111).unwrap();
+        assert_eq!(bits.get_value(), 0b111_0000);
+        assert_eq!(bits.get_field(4..7), Ok(0b111));
+        assert_eq!(bits.get_field(5..7), Ok(0b11));
+
+        bits.set_field(0..32, u32::MAX).unwrap();
+        assert_eq!(bits.get_field(0..32), Ok(u32::MAX));
+        assert_eq!(bits.get_field(31..32), Ok(1));
+
+        bits.set_field(8..16, 0).unwrap();
+        assert_eq!(bits.get_value(), 0xFFFF_00FF);
+
+        assert_eq!(bits.get_field(32..32), Ok(0));
+        assert_eq!(bits.set_field(3..3, 0), Ok(()));
+    }
+
+    #[test]
+    fn field_errors() {
+        let mut bits = Bits::from_value(0xA5_u8);
+
+        assert_eq!(
+            bits.get_field(4..9),
+            Err(BitError::InvalidRange {
+                start: 4,
+                end: 9,
+                width: 8
+            })
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 6..2;
+        assert!(bits.set_field(reversed, 0).is_err());
+        assert_eq!(
+            bits.set_field(0..2, 4),
+            Err(BitError::FieldOverflow { field_width: 2 })
+        );
+        assert_eq!(
+            bits.set_field(2..2, 1),
+            Err(BitError::FieldOverflow { field_width: 0 })
+        );
+        assert!(bits.with_field(0..4, 16).is_err());
+        assert_eq!(bits.get_value(), 0xA5);
+    }
+}
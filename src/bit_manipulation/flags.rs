@@ -0,0 +1,427 @@
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "alloc")]
+use crate::bit_manipulation::BitError;
+use crate::bit_manipulation::Bits;
+use crate::private;
+
+/// A table of named flags, used to parse and format flag expressions such as `READ | WRITE`.
+///
+/// Each entry maps a name to the bits it stands for. An entry may hold several bits, which makes
+/// it a composite alias such as `RW = READ | WRITE`.
+///
+/// Expressions combine names with `|` (union), `&` (intersection), `!` (complement) and
+/// parentheses, with the usual precedence: `!` binds tighter than `&`, which binds tighter than
+/// `|`. The complement is taken among the named bits, so `!EXEC` holds every named flag but
+/// `EXEC`. Numbers written with the syntax of [`Bits::from_str`](core::str::FromStr), such as
+/// `0x80`, stand for bits without a name. Whitespace is ignored and an empty expression is the
+/// empty set. Parentheses and `!` nest at most [`FlagTable::MAX_DEPTH`] levels deep.
+///
+/// A name is a run of ASCII letters, digits and underscores. A word starting with a digit is
+/// always parsed as a number, so an entry whose name starts with a digit, such as `2FA`, can be
+/// formatted but cannot be referenced in an expression.
+///
+/// Parsing and formatting need the `alloc` feature.
+///
+/// # Example
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// use bit_manipulation::bit_manipulation::{Bits, FlagTable};
+///
+/// const PERMISSIONS: FlagTable<'static, u8> = FlagTable::new(&[
+///     ("RW", Bits::from_value(0b011)),
+///     ("READ", Bits::from_value(0b001)),
+///     ("WRITE", Bits::from_value(0b010)),
+///     ("EXEC", Bits::from_value(0b100)),
+/// ]);
+///
+/// let bits = PERMISSIONS.parse("READ | WRITE")?;
+/// assert_eq!(bits.get_value(), 0b011);
+/// assert_eq!(PERMISSIONS.parse("!EXEC")?, PERMISSIONS.parse("RW")?);
+/// assert_eq!(PERMISSIONS.format(bits), "RW");
+/// assert_eq!(PERMISSIONS.format(Bits::from_value(0b1000_0101)), "READ | EXEC | 0x80");
+/// # Ok::<(), bit_manipulation::bit_manipulation::BitError>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FlagTable<'a, T>
+where
+    T: private::Uint,
+{
+    entries: &'a [(&'a str, Bits<T>)],
+}
+
+impl<'a, T> FlagTable<'a, T>
+where
+    T: private::Uint,
+{
+    /// The deepest nesting of parentheses and `!` accepted by `FlagTable::parse`.
+    ///
+    /// The parser is recursive, so the limit keeps untrusted expressions from exhausting the
+    /// stack.
+    pub const MAX_DEPTH: usize = 64;
+
+    /// Creates a table from `(name, bits)` entries.
+    ///
+    /// `FlagTable::format` tries the entries in order, so composite aliases listed first are
+    /// preferred over the flags they are made of.
+    pub const fn new(entries: &'a [(&'a str, Bits<T>)]) -> Self {
+        FlagTable { entries }
+    }
+
+    /// Returns the entries of the table, in order.
+    pub fn entries(&self) -> &'a [(&'a str, Bits<T>)] {
+        self.entries
+    }
+
+    /// Returns the bits named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Bits<T>> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| *entry == name)
+            .map(|&(_, bits)| bits)
+    }
+
+    /// Returns every named bit.
+    pub fn all(&self) -> Bits<T> {
+        self.entries
+            .iter()
+            .fold(Bits::new(), |all, &(_, bits)| all | bits)
+    }
+
+    /// Parses a flag expression.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(bits)` holding the value of the expression.
+    /// - `Err(BitError::UnknownFlag)` if a name is not in the table.
+    /// - `Err(BitError::UnexpectedCharacter)` or `Err(BitError::UnexpectedEnd)` if the expression
+    ///   is malformed.
+    /// - `Err(BitError::NestingTooDeep)` if a `(` or `!` is nested deeper than
+    ///   [`FlagTable::MAX_DEPTH`].
+    /// - Any error of [`Bits::from_str`](core::str::FromStr) for a malformed number.
+    ///
+    /// Every error that points at the expression carries a column, starting from 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitError, Bits, FlagTable};
+    ///
+    /// let entries = [("READ", Bits::from_value(1_u8))];
+    /// let table = FlagTable::new(&entries);
+    /// assert_eq!(
+    ///     table.parse("READ | WRTE"),
+    ///     Err(BitError::UnknownFlag { name: "WRTE".to_string(), column: 8 })
+    /// );
+    /// assert_eq!(table.parse("(READ"), Err(BitError::UnexpectedEnd { column: 6 }));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn parse(&self, text: &str) -> Result<Bits<T>, BitError> {
+        let mut parser = Parser {
+            table: self,
+            chars: text.chars().collect(),
+            position: 0,
+            depth: 0,
+        };
+        if parser.peek().is_none() {
+            return Ok(Bits::new());
+        }
+        let bits = parser.expression()?;
+        match parser.peek() {
+            Some(character) => Err(BitError::UnexpectedCharacter {
+                character,
+                column: parser.column(),
+            }),
+            None => Ok(bits),
+        }
+    }
+
+    /// Formats `bits` as a canonical flag expression.
+    ///
+    /// Entries are taken in table order: an entry is written if all its bits are set and at
+    /// least one of them was not written yet. Bits without a name are written last, as a
+    /// hexadecimal number. The empty set is written as an empty string. Parsing the result with
+    /// [`FlagTable::parse`] gives `bits` back.
+    #[cfg(feature = "alloc")]
+    pub fn format(&self, bits: Bits<T>) -> String {
+        self.names(bits).join(" | ")
+    }
+
+    /// Returns the terms of the canonical expression of `bits`, see [`FlagTable::format`].
+    #[cfg(feature = "alloc")]
+    pub(crate) fn names(&self, bits: Bits<T>) -> Vec<String> {
+        let mut remaining = bits;
+        let mut names = Vec::new();
+        for &(name, flag) in self.entries {
+            if flag.is_subset(&bits) && !flag.is_disjoint(&remaining) {
+                names.push(name.to_string());
+                remaining -= flag;
+            }
+        }
+        if remaining != Bits::new() {
+            names.push(format!("{remaining:#x}"));
+        }
+        names
+    }
+}
+
+/// A recursive descent parser over the characters of a flag expression.
+#[cfg(feature = "alloc")]
+struct Parser<'p, 'a, T>
+where
+    T: private::Uint,
+{
+    table: &'p FlagTable<'a, T>,
+    chars: Vec<char>,
+    position: usize,
+    /// The number of `(` and `!` enclosing the current position.
+    depth: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Parser<'_, '_, T>
+where
+    T: private::Uint,
+{
+    /// Returns the next character that is not whitespace, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|character| character.is_whitespace())
+        {
+            self.position += 1;
+        }
+        self.chars.get(self.position).copied()
+    }
+
+    fn column(&self) -> usize {
+        self.position + 1
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expression(&mut self) -> Result<Bits<T>, BitError> {
+        let mut bits = self.term()?;
+        while self.eat('|') {
+            bits |= self.term()?;
+        }
+        Ok(bits)
+    }
+
+    fn term(&mut self) -> Result<Bits<T>, BitError> {
+        let mut bits = self.factor()?;
+        while self.eat('&') {
+            bits &= self.factor()?;
+        }
+        Ok(bits)
+    }
+
+    fn factor(&mut self) -> Result<Bits<T>, BitError> {
+        let next = self.peek();
+        let column = self.column();
+        match next {
+            Some('!') => {
+                self.enter(column)?;
+                let bits = self.table.all() - self.factor()?;
+                self.depth -= 1;
+                Ok(bits)
+            }
+            Some('(') => {
+                self.enter(column)?;
+                let bits = self.expression()?;
+                if !self.eat(')') {
+                    return Err(self.unexpected());
+                }
+                self.depth -= 1;
+                Ok(bits)
+            }
+            Some(character) if character.is_ascii_alphabetic() || character == '_' => {
+                let name = self.word();
+                self.table
+                    .get(&name)
+                    .ok_or(BitError::UnknownFlag { name, column })
+            }
+            Some(character) if character.is_ascii_digit() => {
+                let number = self.word();
+                number.parse().map_err(|error| match error {
+                    BitError::InvalidDigit { digit, index } => BitError::UnexpectedCharacter {
+                        character: digit,
+                        column: column + index,
+                    },
+                    BitError::MissingDigits => BitError::UnexpectedEnd {
+                        column: column + number.len(),
+                    },
+                    error => error,
+                })
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Consumes the `(` or `!` at `column` and enters one more nesting level, unless the
+    /// expression is already nested [`FlagTable::MAX_DEPTH`] levels deep.
+    fn enter(&mut self, column: usize) -> Result<(), BitError> {
+        if self.depth == FlagTable::<T>::MAX_DEPTH {
+            return Err(BitError::NestingTooDeep { column });
+        }
+        self.position += 1;
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Consumes a run of ASCII letters, digits and underscores.
+    fn word(&mut self) -> String {
+        let start = self.position;
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|&character| character.is_ascii_alphanumeric() || character == '_')
+        {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    /// Returns the error for the character at the current position, or for the end of input.
+    fn unexpected(&mut self) -> BitError {
+        match self.peek() {
+            Some(character) => BitError::UnexpectedCharacter {
+                character,
+                column: self.column(),
+            },
+            None => BitError::UnexpectedEnd {
+                column: self.column(),
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    const TABLE: FlagTable<'static, u16> = FlagTable::new(&[
+        ("ALL", Bits::from_value(0b1111)),
+        ("READ", Bits::from_value(0b0001)),
+        ("WRITE", Bits::from_value(0b0010)),
+        ("EXEC", Bits::from_value(0b0100)),
+        ("ADMIN", Bits::from_value(0b1000)),
+    ]);
+
+    #[test]
+    fn parse_expressions() {
+        assert_eq!(TABLE.parse(""), Ok(Bits::new()));
+        assert_eq!(TABLE.parse("READ|WRITE").unwrap().get_value(), 0b0011);
+        assert_eq!(TABLE.parse(" !EXEC ").unwrap().get_value(), 0b1011);
+        assert_eq!(
+            TABLE.parse("READ | WRITE & !WRITE").unwrap().get_value(),
+            0b0001
+        );
+        assert_eq!(
+            TABLE.parse("(READ | WRITE) & !WRITE").unwrap().get_value(),
+            0b0001
+        );
+        assert_eq!(TABLE.parse("!!ADMIN").unwrap().get_value(), 0b1000);
+        assert_eq!(
+            TABLE.parse("ALL & !READ | 0x100").unwrap().get_value(),
+            0x10E
+        );
+    }
+
+    #[test]
+    fn report_columns() {
+        assert_eq!(
+            TABLE.parse("READ | ROOT"),
+            Err(BitError::UnknownFlag {
+                name: "ROOT".to_string(),
+                column: 8
+            })
+        );
+        assert_eq!(
+            TABLE.parse("READ WRITE"),
+            Err(BitError::UnexpectedCharacter {
+                character: 'W',
+                column: 6
+            })
+        );
+        assert_eq!(
+            TABLE.parse("READ |"),
+            Err(BitError::UnexpectedEnd { column: 7 })
+        );
+        assert_eq!(
+            TABLE.parse("(READ))"),
+            Err(BitError::UnexpectedCharacter {
+                character: ')',
+                column: 7
+            })
+        );
+        assert_eq!(
+            TABLE.parse("READ | 0x1G"),
+            Err(BitError::UnexpectedCharacter {
+                character: 'G',
+                column: 11
+            })
+        );
+        assert_eq!(
+            TABLE.parse("0x10000"),
+            Err(BitError::OutOfRange { bit: 16, width: 16 })
+        );
+
+        let entries = [("2FA", Bits::from_value(1_u8))];
+        let table = FlagTable::new(&entries);
+        assert_eq!(table.format(Bits::from_value(1)), "2FA");
+        assert_eq!(
+            table.parse("2FA"),
+            Err(BitError::UnexpectedCharacter {
+                character: '2',
+                column: 1
+            })
+        );
+    }
+
+    #[test]
+    fn limit_nesting() {
+        let depth = FlagTable::<u16>::MAX_DEPTH;
+        let nested = format!("{}READ{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(TABLE.parse(&nested).unwrap().get_value(), 0b0001);
+        assert_eq!(
+            TABLE.parse(&format!("{}READ", "!".repeat(depth))),
+            Ok(Bits::from_value(0b0001))
+        );
+
+        assert_eq!(
+            TABLE.parse(&"(".repeat(200_000)),
+            Err(BitError::NestingTooDeep { column: depth + 1 })
+        );
+        assert_eq!(
+            TABLE.parse(&format!("READ | {}", "!(".repeat(100_000))),
+            Err(BitError::NestingTooDeep { column: depth + 8 })
+        );
+    }
+
+    #[test]
+    fn format_canonically() {
+        assert_eq!(TABLE.format(Bits::new()), "");
+        assert_eq!(TABLE.format(Bits::from_value(0b1111)), "ALL");
+        assert_eq!(TABLE.format(Bits::from_value(0b0101)), "READ | EXEC");
+        assert_eq!(TABLE.format(Bits::from_value(0xF010)), "0xf010");
+
+        for value in [0, 0b0110, 0b1111, 0x8009] {
+            let bits = Bits::from_value(value);
+            assert_eq!(TABLE.parse(&TABLE.format(bits)), Ok(bits));
+        }
+    }
+}
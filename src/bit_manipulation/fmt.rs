@@ -0,0 +1,303 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::bit_manipulation::{BitError, Bits};
+use crate::private;
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Writes the value in base `2^bits_per_digit`, without leading zeros, through
+    /// [`fmt::Formatter::pad_integral`] so that width, fill, `0` and `#` flags are honoured.
+    ///
+    /// The digits are built in a stack buffer, which fits the 255 binary digits of the widest
+    /// possible `T`.
+    fn fmt_radix(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        bits_per_digit: u8,
+        uppercase: bool,
+        prefix: &str,
+    ) -> fmt::Result {
+        let width = T::in_memory_size();
+        let mut digits = [b'0'; u8::MAX as usize];
+        let mut len = 0;
+        for digit in (0..width.div_ceil(bits_per_digit)).rev() {
+            let mut value = 0;
+            for bit in 0..bits_per_digit {
+                if self.is_bit_on(digit * bits_per_digit + bit) {
+                    value |= 1 << bit;
+                }
+            }
+            if value == 0 && len == 0 {
+                continue;
+            }
+            let digit = char::from_digit(value, 1 << bits_per_digit).unwrap() as u8;
+            digits[len] = if uppercase {
+                digit.to_ascii_uppercase()
+            } else {
+                digit
+            };
+            len += 1;
+        }
+        // Only ASCII digits are written, and a zero value keeps the leading `0` of the buffer.
+        let digits = core::str::from_utf8(&digits[..len.max(1)]).unwrap();
+        f.pad_integral(true, prefix, digits)
+    }
+}
+
+impl<T> fmt::Display for Bits<T>
+where
+    T: private::Uint,
+{
+    /// Renders the bit pattern of the value, most significant bit first, using one digit per
+    /// bit of `T`.
+    ///
+    /// The alternate form (`{:#}`) adds a `0b` prefix and separates groups of 4 bits with
+    /// underscores, which [`Bits::from_str`] parses back. The pattern is written through
+    /// [`fmt::Formatter::pad`], so width, fill and alignment are honoured.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1010_0110_u8);
+    /// assert_eq!(bits.to_string(), "10100110");
+    /// assert_eq!(format!("{bits:#}"), "0b1010_0110");
+    /// assert_eq!(format!("{bits:>10}"), "  10100110");
+    /// assert_eq!(Bits::from_value(5_u16).to_string(), "0000000000000101");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The prefix, 255 digits and an underscore every 4 digits for the widest possible `T`.
+        let mut text = [0; 2 + 255 + 255 / 4];
+        let mut len = 0;
+        let mut push = |byte| {
+            text[len] = byte;
+            len += 1;
+        };
+        if f.alternate() {
+            push(b'0');
+            push(b'b');
+        }
+        let width = T::in_memory_size();
+        for (index, on) in self.get_all_bits().rev().enumerate() {
+            if f.alternate() && index > 0 && (width as usize - index).is_multiple_of(4) {
+                push(b'_');
+            }
+            push(if on { b'1' } else { b'0' });
+        }
+        // Only ASCII characters are written.
+        f.pad(core::str::from_utf8(&text[..len]).unwrap())
+    }
+}
+
+impl<T> fmt::Binary for Bits<T>
+where
+    T: private::Uint,
+{
+    /// Formats the value like an integer, without leading zeros.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0110_u8);
+    /// assert_eq!(format!("{bits:b}"), "110");
+    /// assert_eq!(format!("{bits:#010b}"), "0b00000110");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_radix(f, 1, false, "0b")
+    }
+}
+
+impl<T> fmt::Octal for Bits<T>
+where
+    T: private::Uint,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_radix(f, 3, false, "0o")
+    }
+}
+
+impl<T> fmt::LowerHex for Bits<T>
+where
+    T: private::Uint,
+{
+    /// Formats the value like an integer, without leading zeros.
+    ///
+    /// Signed integers are formatted as raw bit patterns, like the standard library does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// assert_eq!(format!("{:x}", Bits::from_value(0xBEEF_u16)), "beef");
+    /// assert_eq!(format!("{:#x}", Bits::from_value(-1_i8)), "0xff");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_radix(f, 4, false, "0x")
+    }
+}
+
+impl<T> fmt::UpperHex for Bits<T>
+where
+    T: private::Uint,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_radix(f, 4, true, "0x")
+    }
+}
+
+impl<T> FromStr for Bits<T>
+where
+    T: private::Uint,
+{
+    type Err = BitError;
+
+    /// Parses a bit pattern written in binary, octal or hexadecimal.
+    ///
+    /// The base is given by a `0b`, `0o` or `0x` prefix; text without prefix is read as binary,
+    /// so that the output of `Display` parses back. Underscores may separate digits, and leading
+    /// zeros are accepted whatever the width of `T`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(bits)` holding the parsed value.
+    /// - `Err(BitError::InvalidDigit)` with the byte offset of the first character that is not a
+    ///   digit of the base.
+    /// - `Err(BitError::MissingDigits)` if there is no digit after the prefix.
+    /// - `Err(BitError::OutOfRange)` if the number needs more bits than `T` has.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{BitError, Bits};
+    ///
+    /// let bits: Bits<u8> = "0b1010_0110".parse()?;
+    /// assert_eq!(bits.get_value(), 0b1010_0110);
+    /// assert_eq!("0xA6".parse(), Ok(bits));
+    /// assert_eq!(
+    ///     "0x1A6".parse::<Bits<u8>>(),
+    ///     Err(BitError::OutOfRange { bit: 8, width: 8 })
+    /// );
+    /// assert_eq!(
+    ///     "0o7_9".parse::<Bits<u8>>(),
+    ///     Err(BitError::InvalidDigit { digit: '9', index: 4 })
+    /// );
+    /// # Ok::<(), BitError>(())
+    /// ```
+    fn from_str(text: &str) -> Result<Self, BitError> {
+        let (bits_per_digit, offset) = match text.get(..2) {
+            Some("0b" | "0B") => (1, 2),
+            Some("0o" | "0O") => (3, 2),
+            Some("0x" | "0X") => (4, 2),
+            _ => (1, 0),
+        };
+        let digits = &text[offset..];
+        let radix = 1 << bits_per_digit;
+
+        if let Some((index, digit)) = digits
+            .char_indices()
+            .find(|&(_, digit)| digit != '_' && !digit.is_digit(radix))
+        {
+            return Err(BitError::InvalidDigit {
+                digit,
+                index: offset + index,
+            });
+        }
+
+        let width = T::in_memory_size() as usize;
+        let mut bits = Bits::new();
+        let mut position = 0;
+        let mut has_digits = false;
+        for digit in digits
+            .chars()
+            .rev()
+            .filter_map(|digit| digit.to_digit(radix))
+        {
+            has_digits = true;
+            for bit in 0..bits_per_digit {
+                if digit & (1 << bit) == 0 {
+                    continue;
+                }
+                let bit = position + bit as usize;
+                if bit >= width {
+                    return Err(BitError::OutOfRange { bit, width });
+                }
+                bits.set_bit(bit as u8);
+            }
+            position += bits_per_digit as usize;
+        }
+        if !has_digits {
+            return Err(BitError::MissingDigits);
+        }
+        Ok(bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Bits::<u8>::new().to_string(), "00000000");
+        assert_eq!(Bits::from_value(u8::MAX).to_string(), "11111111");
+        assert_eq!(Bits::from_value(1_u32 << 31).to_string().len(), 32);
+        assert!(Bits::from_value(1_u128 << 127)
+            .to_string()
+            .starts_with("10"));
+        assert_eq!(
+            format!("{:#}", Bits::from_value(0x8421_u16)),
+            "0b1000_0100_0010_0001"
+        );
+
+        let bits = Bits::from_value(0b0110_u8);
+        assert_eq!(format!("{bits:>12}"), "    00000110");
+        assert_eq!(format!("{bits:*<10}"), "00000110**");
+        assert_eq!(format!("{bits:^#15}"), "  0b0000_0110  ");
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let bits = Bits::from_value(0o1234_u16);
+        assert_eq!(format!("{bits:o}"), "1234");
+        assert_eq!(format!("{bits:#o}"), "0o1234");
+        assert_eq!(format!("{bits:X}"), "29C");
+        assert_eq!(format!("{bits:06x}"), "00029c");
+        assert_eq!(format!("{:b}", Bits::<u8>::new()), "0");
+        assert_eq!(format!("{:x}", Bits::from_value(u128::MAX)).len(), 32);
+        assert_eq!(format!("{:o}", Bits::from_value(u8::MAX)), "377");
+    }
+
+    #[test]
+    fn parse() {
+        for value in [0_u16, 1, 0x8000, 0xBEEF, u16::MAX] {
+            let bits = Bits::from_value(value);
+            assert_eq!(bits.to_string().parse(), Ok(bits));
+            assert_eq!(format!("{bits:#}").parse(), Ok(bits));
+            assert_eq!(format!("{bits:#X}").parse(), Ok(bits));
+            assert_eq!(format!("{bits:#o}").parse(), Ok(bits));
+        }
+
+        assert_eq!("0000_0000_0000_0001".parse(), Ok(Bits::from_value(1_u8)));
+        assert_eq!("0x".parse::<Bits<u8>>(), Err(BitError::MissingDigits));
+        assert_eq!("_".parse::<Bits<u8>>(), Err(BitError::MissingDigits));
+        assert_eq!(
+            "0b102".parse::<Bits<u8>>(),
+            Err(BitError::InvalidDigit {
+                digit: '2',
+                index: 4
+            })
+        );
+        assert_eq!(
+            "0o400".parse::<Bits<u8>>(),
+            Err(BitError::OutOfRange { bit: 8, width: 8 })
+        );
+        assert_eq!("0xff".parse(), Ok(Bits::from_value(-1_i8)));
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use crate::bit_manipulation::{Bits, Mask};
+use crate::private;
+
+/// A [`Bits`] value whose changes can be awaited.
+///
+/// [`AsyncBits::wait_until`] returns a future that completes once a predicate over the value
+/// holds, and [`AsyncBits::wait_all`] / [`AsyncBits::wait_any`] cover the usual mask
+/// predicates. Every change made through [`AsyncBits::set_bits`], [`AsyncBits::clear_bits`] or
+/// [`AsyncBits::store`] wakes the pending futures, which check their predicate again when
+/// polled.
+///
+/// The futures only rely on [`core::task`], so they run on any executor. Each pending future
+/// registers its own waker, and dropping a future removes it, so a wait can be cancelled at any
+/// point, for example by a `select!` or a timeout of the runtime.
+///
+/// The value is guarded by a [`Mutex`], so this type needs the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::{AsyncBits, Mask};
+/// # use std::future::Future;
+/// # use std::sync::Arc;
+/// # use std::task::{Context, Poll, Wake, Waker};
+/// # use std::thread::{self, Thread};
+/// #
+/// # struct Unpark(Thread);
+/// #
+/// # impl Wake for Unpark {
+/// #     fn wake(self: Arc<Self>) {
+/// #         self.0.unpark();
+/// #     }
+/// # }
+/// #
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = std::pin::pin!(future);
+/// #     let waker = Waker::from(Arc::new(Unpark(thread::current())));
+/// #     let mut cx = Context::from_waker(&waker);
+/// #     loop {
+/// #         if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #         thread::park();
+/// #     }
+/// # }
+///
+/// const CONNECTED: Mask<u8> = Mask::from_value(0b01);
+/// const AUTHENTICATED: Mask<u8> = Mask::from_value(0b10);
+///
+/// let state = AsyncBits::<u8>::default();
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| {
+///         state.set_bits(CONNECTED);
+///         state.set_bits(AUTHENTICATED);
+///     });
+///
+///     let bits = block_on(state.wait_all(CONNECTED | AUTHENTICATED));
+///     assert_eq!(bits.get_value(), 0b11);
+/// });
+/// ```
+pub struct AsyncBits<T>
+where
+    T: private::Uint,
+{
+    state: Mutex<State<T>>,
+}
+
+struct State<T>
+where
+    T: private::Uint,
+{
+    bits: Bits<T>,
+    /// The waker of every pending future, by key.
+    waiters: BTreeMap<u64, Waker>,
+    next_key: u64,
+}
+
+impl<T> AsyncBits<T>
+where
+    T: private::Uint,
+{
+    /// Creates an awaitable value holding `bits`.
+    pub fn new(bits: Bits<T>) -> Self {
+        AsyncBits {
+            state: Mutex::new(State {
+                bits,
+                waiters: BTreeMap::new(),
+                next_key: 0,
+            }),
+        }
+    }
+
+    /// Returns the current bits.
+    pub fn get(&self) -> Bits<T> {
+        self.lock().bits
+    }
+
+    /// Replaces the current bits with `bits` and wakes the pending futures.
+    ///
+    /// # Returns
+    ///
+    /// The bits before the call.
+    pub fn store(&self, bits: Bits<T>) -> Bits<T> {
+        self.update(|current| *current = bits)
+    }
+
+    /// Turns on every bit selected by `mask` and wakes the pending futures.
+    ///
+    /// # Returns
+    ///
+    /// The bits before the call.
+    pub fn set_bits(&self, mask: Mask<T>) -> Bits<T> {
+        self.update(|bits| bits.apply_mask(mask))
+    }
+
+    /// Turns off every bit selected by `mask` and wakes the pending futures.
+    ///
+    /// # Returns
+    ///
+    /// The bits before the call.
+    pub fn clear_bits(&self, mask: Mask<T>) -> Bits<T> {
+        self.update(|bits| bits.clear_mask(mask))
+    }
+
+    /// Returns a future that completes once `predicate` holds for the current bits.
+    ///
+    /// The predicate is called with the lock of the value held, when the future is polled, so it
+    /// must not use this `AsyncBits`. The future resolves to the bits that satisfied it.
+    ///
+    /// # Parameters
+    ///
+    /// - `predicate`: The condition to wait for.
+    ///
+    /// # Returns
+    ///
+    /// A [`WaitUntil`] future, which does nothing until it is polled.
+    pub fn wait_until<F>(&self, predicate: F) -> WaitUntil<'_, T, F>
+    where
+        F: FnMut(Bits<T>) -> bool,
+    {
+        WaitUntil {
+            bits: self,
+            predicate,
+            key: None,
+        }
+    }
+
+    /// Returns a future that completes once every bit selected by `mask` is set.
+    pub fn wait_all(&self, mask: Mask<T>) -> WaitUntil<'_, T, impl FnMut(Bits<T>) -> bool> {
+        self.wait_until(move |bits| bits.masked_eq(mask, mask.value()))
+    }
+
+    /// Returns a future that completes once at least one bit selected by `mask` is set.
+    pub fn wait_any(&self, mask: Mask<T>) -> WaitUntil<'_, T, impl FnMut(Bits<T>) -> bool> {
+        self.wait_until(move |bits| !bits.masked_eq(mask, T::ZERO))
+    }
+
+    /// Applies `change` to the bits, then wakes every pending future once the lock is released.
+    fn update(&self, change: impl FnOnce(&mut Bits<T>)) -> Bits<T> {
+        let mut state = self.lock();
+        let previous = state.bits;
+        change(&mut state.bits);
+        if state.bits == previous {
+            return previous;
+        }
+        let waiters = std::mem::take(&mut state.waiters);
+        drop(state);
+        for waker in waiters.into_values() {
+            waker.wake();
+        }
+        previous
+    }
+
+    /// Locks the state, ignoring poisoning: the state is only changed by operations that cannot
+    /// panic, apart from the predicates, which do not touch it.
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Default for AsyncBits<T>
+where
+    T: private::Uint,
+{
+    fn default() -> Self {
+        AsyncBits::new(Bits::new())
+    }
+}
+
+impl<T> fmt::Debug for AsyncBits<T>
+where
+    T: private::Uint + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsyncBits").field(&self.get()).finish()
+    }
+}
+
+/// The future returned by [`AsyncBits::wait_until`], [`AsyncBits::wait_all`] and
+/// [`AsyncBits::wait_any`].
+///
+/// It resolves to the bits that satisfied the predicate. Dropping it before completion
+/// unregisters its waker.
+#[must_use = "futures do nothing unless polled"]
+pub struct WaitUntil<'a, T, F>
+where
+    T: private::Uint,
+{
+    bits: &'a AsyncBits<T>,
+    predicate: F,
+    /// The key of the registered waker, once the future has been pending.
+    key: Option<u64>,
+}
+
+// The predicate is never pinned: it is only called through `&mut`, so moving the future is fine.
+impl<T, F> Unpin for WaitUntil<'_, T, F> where T: private::Uint {}
+
+impl<T, F> Future for WaitUntil<'_, T, F>
+where
+    T: private::Uint,
+    F: FnMut(Bits<T>) -> bool,
+{
+    type Output = Bits<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Bits<T>> {
+        let this = self.get_mut();
+        let mut state = this.bits.lock();
+        if (this.predicate)(state.bits) {
+            if let Some(key) = this.key.take() {
+                state.waiters.remove(&key);
+            }
+            return Poll::Ready(state.bits);
+        }
+
+        let key = *this.key.get_or_insert_with(|| {
+            state.next_key += 1;
+            state.next_key
+        });
+        match state.waiters.get_mut(&key) {
+            Some(waker) => waker.clone_from(cx.waker()),
+            None => {
+                state.waiters.insert(key, cx.waker().clone());
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<T, F> Drop for WaitUntil<'_, T, F>
+where
+    T: private::Uint,
+{
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.bits.lock().waiters.remove(&key);
+        }
+    }
+}
+
+impl<T, F> fmt::Debug for WaitUntil<'_, T, F>
+where
+    T: private::Uint,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitUntil").field("key", &self.key).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+
+    use super::*;
+
+    /// Wakes a thread parked by [`block_on`].
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// A minimal executor running a single future on the current thread.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    /// Counts its wakes.
+    #[derive(Default)]
+    struct Counter(AtomicUsize);
+
+    impl Wake for Counter {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn resolve_across_threads() {
+        let bits = AsyncBits::<u32>::default();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for bit in 0..8 {
+                    bits.set_bits(Mask::from_value(1 << bit));
+                }
+            });
+            let all = block_on(bits.wait_all(Mask::from_value(0xF0)));
+            assert!(all.masked_eq(Mask::from_value(0xF0), 0xF0));
+            let counted = block_on(bits.wait_until(|bits| bits.iter_ones().len() == 8));
+            assert_eq!(counted.get_value(), 0xFF);
+        });
+        assert_eq!(
+            block_on(bits.wait_any(Mask::from_value(0x0101))).get_value(),
+            0xFF
+        );
+    }
+
+    #[test]
+    fn per_waiter_wakers_and_cancellation() {
+        let bits = AsyncBits::<u8>::default();
+        let first = Arc::new(Counter::default());
+        let second = Arc::new(Counter::default());
+
+        let mut high = bits.wait_any(Mask::from_value(0x80));
+        let mut low = bits.wait_any(Mask::from_value(0x01));
+        let first_waker = Waker::from(first.clone());
+        let second_waker = Waker::from(second.clone());
+        assert!(Pin::new(&mut high)
+            .poll(&mut Context::from_waker(&first_waker))
+            .is_pending());
+        assert!(Pin::new(&mut low)
+            .poll(&mut Context::from_waker(&second_waker))
+            .is_pending());
+        assert_eq!(bits.lock().waiters.len(), 2);
+
+        bits.set_bits(Mask::from_value(0x01));
+        assert_eq!(first.0.load(Ordering::SeqCst), 1);
+        assert_eq!(second.0.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            Pin::new(&mut low).poll(&mut Context::from_waker(&second_waker)),
+            Poll::Ready(Bits::from_value(0x01))
+        );
+        assert!(Pin::new(&mut high)
+            .poll(&mut Context::from_waker(&first_waker))
+            .is_pending());
+
+        drop(high);
+        assert!(bits.lock().waiters.is_empty());
+        bits.clear_bits(Mask::from_value(0x01));
+        assert_eq!(first.0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,273 @@
+use core::ops::{Shl, ShlAssign, Shr, ShrAssign};
+
+use crate::bit_manipulation::Bits;
+use crate::private;
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Returns the number of bits that are set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0110_0001_u8);
+    /// assert_eq!(bits.count_ones(), 3);
+    /// assert_eq!(bits.count_zeros(), 5);
+    /// ```
+    pub fn count_ones(&self) -> u32 {
+        self.value.count_ones()
+    }
+
+    /// Returns the number of bits that are not set.
+    pub fn count_zeros(&self) -> u32 {
+        T::BITS as u32 - self.count_ones()
+    }
+
+    /// Returns the number of cleared bits above the highest set bit, or the bit width of `T` if
+    /// no bit is set.
+    pub fn leading_zeros(&self) -> u32 {
+        self.value.leading_zeros()
+    }
+
+    /// Returns the number of cleared bits below the lowest set bit, or the bit width of `T` if no
+    /// bit is set.
+    pub fn trailing_zeros(&self) -> u32 {
+        self.value.trailing_zeros()
+    }
+
+    /// Returns the position of the lowest set bit, or `None` if no bit is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0110_1000_u8);
+    /// assert_eq!(bits.lowest_set_bit(), Some(3));
+    /// assert_eq!(bits.highest_set_bit(), Some(6));
+    /// assert_eq!(Bits::<u8>::new().lowest_set_bit(), None);
+    /// ```
+    pub fn lowest_set_bit(&self) -> Option<u8> {
+        if self.value == T::ZERO {
+            return None;
+        }
+        Some(self.trailing_zeros() as u8)
+    }
+
+    /// Returns the position of the highest set bit, or `None` if no bit is set.
+    pub fn highest_set_bit(&self) -> Option<u8> {
+        if self.value == T::ZERO {
+            return None;
+        }
+        Some(T::BITS - 1 - self.leading_zeros() as u8)
+    }
+
+    /// Returns a value holding only the lowest set bit, or no bit if none is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0110_1000_u8);
+    /// assert_eq!(bits.isolate_lowest().get_value(), 0b0000_1000);
+    /// assert_eq!(bits.clear_lowest().get_value(), 0b0110_0000);
+    /// ```
+    pub fn isolate_lowest(&self) -> Self {
+        match self.lowest_set_bit() {
+            Some(bit) => Bits::from_value(T::convert(bit)),
+            None => *self,
+        }
+    }
+
+    /// Returns the value with its lowest set bit cleared, or unchanged if no bit is set.
+    pub fn clear_lowest(&self) -> Self {
+        *self - self.isolate_lowest()
+    }
+
+    /// Returns the value with its bits rotated left by `n` positions.
+    ///
+    /// The bits shifted out on the most significant side come back on the least significant
+    /// side, and `n` may exceed the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1000_0011_u8);
+    /// assert_eq!(bits.rotate_left(1).get_value(), 0b0000_0111);
+    /// assert_eq!(bits.rotate_right(1).get_value(), 0b1100_0001);
+    /// ```
+    pub fn rotate_left(&self, n: u32) -> Self {
+        Bits::from_value(self.value.rotate_left(n))
+    }
+
+    /// Returns the value with its bits rotated right by `n` positions, see
+    /// [`Bits::rotate_left`].
+    pub fn rotate_right(&self, n: u32) -> Self {
+        Bits::from_value(self.value.rotate_right(n))
+    }
+
+    /// Returns the value with the order of its bits reversed: bit 0 becomes bit `n - 1` and so
+    /// on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0000_0110_u8);
+    /// assert_eq!(bits.reverse_bits().get_value(), 0b0110_0000);
+    /// ```
+    pub fn reverse_bits(&self) -> Self {
+        Bits::from_value(self.value.reverse_bits())
+    }
+
+    /// Returns the value with the order of its bytes reversed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0x1234_u16);
+    /// assert_eq!(bits.swap_bytes().get_value(), 0x3412);
+    /// ```
+    pub fn swap_bytes(&self) -> Self {
+        Bits::from_value(self.value.swap_bytes())
+    }
+
+    /// Moves every bit `n` positions up, dropping the bits shifted past the most significant one.
+    fn shift_left(&self, n: u32) -> Self {
+        if n >= T::BITS as u32 {
+            return Bits::new();
+        }
+        Bits::from_value(self.value << n as u8)
+    }
+
+    /// Moves every bit `n` positions down, dropping the bits shifted past bit 0.
+    ///
+    /// The shift is logical for signed integers as well: the vacated high bits are cleared
+    /// rather than filled with the sign bit.
+    fn shift_right(&self, n: u32) -> Self {
+        if n >= T::BITS as u32 {
+            return Bits::new();
+        }
+        if n == 0 {
+            return *self;
+        }
+        let vacated = T::ONES << (T::BITS - n as u8);
+        Bits::from_value((self.value >> n as u8) & !vacated)
+    }
+}
+
+impl<T> Shl<u32> for Bits<T>
+where
+    T: private::Uint,
+{
+    type Output = Self;
+
+    /// Moves every bit `rhs` positions up.
+    ///
+    /// Bits shifted past the most significant position are dropped, so shifting by the bit
+    /// width of `T` or more gives an empty value instead of overflowing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1100_0011_u8);
+    /// assert_eq!((bits << 2).get_value(), 0b0000_1100);
+    /// assert_eq!((bits >> 4).get_value(), 0b0000_1100);
+    /// assert_eq!((bits << 8).get_value(), 0);
+    /// assert_eq!((Bits::from_value(-128_i8) >> 7).get_value(), 1);
+    /// ```
+    fn shl(self, rhs: u32) -> Self {
+        self.shift_left(rhs)
+    }
+}
+
+impl<T> Shr<u32> for Bits<T>
+where
+    T: private::Uint,
+{
+    type Output = Self;
+
+    /// Moves every bit `rhs` positions down.
+    ///
+    /// Bits shifted past bit 0 are dropped and the vacated high bits are cleared, even for
+    /// signed integers, so the shift never copies the sign bit.
+    fn shr(self, rhs: u32) -> Self {
+        self.shift_right(rhs)
+    }
+}
+
+impl<T> ShlAssign<u32> for Bits<T>
+where
+    T: private::Uint,
+{
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = self.shift_left(rhs);
+    }
+}
+
+impl<T> ShrAssign<u32> for Bits<T>
+where
+    T: private::Uint,
+{
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = self.shift_right(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_and_positions() {
+        let bits = Bits::from_value(0x0FF0_u16);
+        assert_eq!(bits.count_ones(), 8);
+        assert_eq!(bits.count_zeros(), 8);
+        assert_eq!(bits.leading_zeros(), 4);
+        assert_eq!(bits.trailing_zeros(), 4);
+        assert_eq!(bits.lowest_set_bit(), Some(4));
+        assert_eq!(bits.highest_set_bit(), Some(11));
+        assert_eq!(bits.isolate_lowest().get_value(), 0x0010);
+        assert_eq!(bits.clear_lowest().get_value(), 0x0FE0);
+
+        let empty = Bits::<u128>::new();
+        assert_eq!(empty.leading_zeros(), 128);
+        assert_eq!(empty.highest_set_bit(), None);
+        assert_eq!(empty.isolate_lowest(), empty);
+        assert_eq!(empty.clear_lowest(), empty);
+
+        let signed = Bits::from_value(i32::MIN);
+        assert_eq!(signed.highest_set_bit(), Some(31));
+        assert_eq!(signed.isolate_lowest(), signed);
+    }
+
+    #[test]
+    fn rotate_reverse_and_shift() {
+        let bits = Bits::from_value(0x8000_0001_u32);
+        assert_eq!(bits.rotate_left(4).get_value(), 0x0000_0018);
+        assert_eq!(bits.rotate_right(36).get_value(), 0x1800_0000);
+        assert_eq!(bits.reverse_bits(), bits);
+        assert_eq!(bits.swap_bytes().get_value(), 0x0100_0080);
+
+        let mut shifted = Bits::from_value(-1_i16);
+        shifted >>= 12;
+        assert_eq!(shifted.get_value(), 0x000F);
+        shifted <<= 14;
+        assert_eq!(shifted.get_value(), -0x4000);
+        assert_eq!((shifted >> 0), shifted);
+        assert_eq!((shifted >> 16).get_value(), 0);
+        assert_eq!((shifted << u32::MAX).get_value(), 0);
+    }
+}
@@ -0,0 +1,347 @@
+use core::iter::FusedIterator;
+
+use crate::bit_manipulation::Bits;
+use crate::private;
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Returns an iterator over the positions of the bits that are set, in ascending order.
+    ///
+    /// Each step finds the next position with a trailing zero count instead of testing every bit,
+    /// so the cost only depends on the number of set bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1001_0010_u8);
+    /// assert!(bits.iter_ones().eq([1, 4, 7]));
+    /// assert!(bits.iter_ones().rev().eq([7, 4, 1]));
+    /// assert_eq!(bits.iter_ones().len(), 3);
+    /// ```
+    pub fn iter_ones(&self) -> IterOnes<T> {
+        IterOnes {
+            remaining: self.value,
+        }
+    }
+
+    /// Returns an iterator over the positions of the bits that are not set, in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1111_0110_u8);
+    /// assert!(bits.iter_zeros().eq([0, 3]));
+    /// ```
+    pub fn iter_zeros(&self) -> IterZeros<T> {
+        IterZeros(IterOnes {
+            remaining: !self.value,
+        })
+    }
+}
+
+/// A lazily computed view over the state of every bit of a [`Bits`](super::Bits) value.
+///
+/// The view yields one boolean per bit, from position 0 to `n - 1`, and holds a copy of the
+/// value it was created from rather than borrowing it.
+#[derive(Debug, Clone)]
+pub struct BitView<T>
+where
+    T: private::Uint,
+{
+    value: T,
+    front: u8,
+    back: u8,
+}
+
+impl<T> BitView<T>
+where
+    T: private::Uint,
+{
+    pub(crate) fn new(value: T) -> Self {
+        BitView {
+            value,
+            front: 0,
+            back: T::in_memory_size(),
+        }
+    }
+
+    fn state(&self, bit: u8) -> bool {
+        self.value & T::convert(bit) != T::ZERO
+    }
+}
+
+impl<T> Iterator for BitView<T>
+where
+    T: private::Uint,
+{
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        let state = self.state(self.front);
+        self.front += 1;
+        Some(state)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for BitView<T>
+where
+    T: private::Uint,
+{
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.state(self.back))
+    }
+}
+
+impl<T> ExactSizeIterator for BitView<T> where T: private::Uint {}
+
+impl<T> FusedIterator for BitView<T> where T: private::Uint {}
+
+/// An iterator telling, for each requested position, whether its bit is set in a [`Bits`] value.
+///
+/// Out of range positions are reported as not set. This struct is created by
+/// [`Bits::are_bits_on`].
+#[derive(Debug, Clone)]
+pub struct BitsOn<T, I>
+where
+    T: private::Uint,
+{
+    bits: Bits<T>,
+    positions: I,
+}
+
+impl<T, I> BitsOn<T, I>
+where
+    T: private::Uint,
+{
+    pub(crate) fn new(bits: Bits<T>, positions: I) -> Self {
+        BitsOn { bits, positions }
+    }
+}
+
+impl<T, I> Iterator for BitsOn<T, I>
+where
+    T: private::Uint,
+    I: Iterator,
+    I::Item: Into<u8>,
+{
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let bit = self.positions.next()?;
+        Some(self.bits.is_bit_on(bit.into()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<T, I> DoubleEndedIterator for BitsOn<T, I>
+where
+    T: private::Uint,
+    I: DoubleEndedIterator,
+    I::Item: Into<u8>,
+{
+    fn next_back(&mut self) -> Option<bool> {
+        let bit = self.positions.next_back()?;
+        Some(self.bits.is_bit_on(bit.into()))
+    }
+}
+
+impl<T, I> ExactSizeIterator for BitsOn<T, I>
+where
+    T: private::Uint,
+    I: ExactSizeIterator,
+    I::Item: Into<u8>,
+{
+}
+
+impl<T, I> FusedIterator for BitsOn<T, I>
+where
+    T: private::Uint,
+    I: FusedIterator,
+    I::Item: Into<u8>,
+{
+}
+
+/// An iterator over the positions of the set bits of a [`Bits`] value.
+///
+/// This struct is created by [`Bits::iter_ones`].
+#[derive(Debug, Clone)]
+pub struct IterOnes<T>
+where
+    T: private::Uint,
+{
+    remaining: T,
+}
+
+impl<T> Iterator for IterOnes<T>
+where
+    T: private::Uint,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == T::ZERO {
+            return None;
+        }
+        let bit = self.remaining.trailing_zeros() as u8;
+        self.remaining &= !T::convert(bit);
+        Some(bit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterOnes<T>
+where
+    T: private::Uint,
+{
+    fn next_back(&mut self) -> Option<u8> {
+        if self.remaining == T::ZERO {
+            return None;
+        }
+        let bit = T::in_memory_size() - 1 - self.remaining.leading_zeros() as u8;
+        self.remaining &= !T::convert(bit);
+        Some(bit)
+    }
+}
+
+impl<T> ExactSizeIterator for IterOnes<T> where T: private::Uint {}
+
+impl<T> FusedIterator for IterOnes<T> where T: private::Uint {}
+
+/// An iterator over the positions of the cleared bits of a [`Bits`] value.
+///
+/// This struct is created by [`Bits::iter_zeros`].
+#[derive(Debug, Clone)]
+pub struct IterZeros<T>(IterOnes<T>)
+where
+    T: private::Uint;
+
+impl<T> Iterator for IterZeros<T>
+where
+    T: private::Uint,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterZeros<T>
+where
+    T: private::Uint,
+{
+    fn next_back(&mut self) -> Option<u8> {
+        self.0.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterZeros<T> where T: private::Uint {}
+
+impl<T> FusedIterator for IterZeros<T> where T: private::Uint {}
+
+impl<T> IntoIterator for &Bits<T>
+where
+    T: private::Uint,
+{
+    type Item = u8;
+    type IntoIter = IterOnes<T>;
+
+    /// Iterates over the positions of the set bits, see [`Bits::iter_ones`].
+    fn into_iter(self) -> IterOnes<T> {
+        self.iter_ones()
+    }
+}
+
+impl<T> FromIterator<u8> for Bits<T>
+where
+    T: private::Uint,
+{
+    /// Builds a `Bits<T>` with the bits at the given positions set.
+    ///
+    /// Out of range positions are skipped, as with [`Bits::set_bit`].
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut bits = Bits::new();
+        bits.extend(iter);
+        bits
+    }
+}
+
+impl<T> Extend<u8> for Bits<T>
+where
+    T: private::Uint,
+{
+    /// Sets the bits at the given positions, skipping out of range positions as with
+    /// [`Bits::set_bit`].
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for bit in iter {
+            self.set_bit(bit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_ones_and_zeros() {
+        let bits = Bits::from_value(1_u128 | 1 << 64 | 1 << 127);
+
+        assert!(bits.iter_ones().eq([0, 64, 127]));
+        assert!(bits.iter_ones().rev().eq([127, 64, 0]));
+        assert_eq!(bits.iter_zeros().len(), 125);
+        assert_eq!(bits.iter_zeros().next(), Some(1));
+        assert_eq!(bits.iter_zeros().next_back(), Some(126));
+
+        let mut ones = bits.iter_ones();
+        assert_eq!(ones.next(), Some(0));
+        assert_eq!(ones.next_back(), Some(127));
+        assert_eq!(ones.len(), 1);
+        assert_eq!(ones.next_back(), Some(64));
+        assert_eq!(ones.next(), None);
+
+        assert_eq!(Bits::<u8>::new().iter_ones().next(), None);
+        assert_eq!(Bits::from_value(u8::MAX).iter_zeros().next(), None);
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let mut bits: Bits<u8> = [1, 3, 42].into_iter().collect();
+        assert_eq!(bits.get_value(), 0b1010);
+
+        bits.extend(5..7);
+        assert_eq!(bits.get_value(), 0b110_1010);
+
+        let positions: Vec<u8> = (&bits).into_iter().collect();
+        assert_eq!(positions, vec![1, 3, 5, 6]);
+    }
+}
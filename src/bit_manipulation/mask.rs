@@ -0,0 +1,217 @@
+use core::ops::{BitOr, Not, RangeBounds};
+
+use crate::bit_manipulation::{BitError, Bits};
+use crate::private;
+
+/// A precomputed set of bit positions, applied to a [`Bits`] value in a single operation.
+///
+/// Building a mask once and applying it with [`Bits::apply_mask`], [`Bits::clear_mask`],
+/// [`Bits::toggle_mask`] or [`Bits::masked_eq`] avoids walking a slice of positions on every call.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::{Bits, Mask};
+///
+/// let dirty = Mask::<u8>::from_bits([1, 5]);
+/// let mut bits = Bits::new();
+/// bits.apply_mask(dirty);
+/// assert_eq!(bits.get_value(), 0b0010_0010);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mask<T>
+where
+    T: private::Uint,
+{
+    value: T,
+}
+
+impl<T> Mask<T>
+where
+    T: private::Uint,
+{
+    /// Creates a mask selecting the bits set in `value`.
+    pub const fn from_value(value: T) -> Self {
+        Mask { value }
+    }
+
+    /// Creates a mask selecting the given positions.
+    ///
+    /// Positions greater than or equal to the bit width of `T` are skipped.
+    pub fn from_bits<I>(bits: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<u8>,
+    {
+        let mut mask = Bits::new();
+        for bit in bits {
+            mask.set_bit(bit.into());
+        }
+        Mask::from_value(mask.get_value())
+    }
+
+    /// Creates a mask selecting every position of a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(mask)` selecting the positions of `range`.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Mask;
+    ///
+    /// assert_eq!(Mask::<u8>::from_range(2..=4).unwrap().value(), 0b0001_1100);
+    /// ```
+    pub fn from_range(range: impl RangeBounds<u8>) -> Result<Self, BitError> {
+        Ok(Mask::from_value(Bits::range_mask(range)?))
+    }
+
+    /// Returns the selected bits as an integer.
+    pub const fn value(&self) -> T {
+        self.value
+    }
+}
+
+impl<T> BitOr for Mask<T>
+where
+    T: private::Uint,
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Mask::from_value(self.value | rhs.value)
+    }
+}
+
+impl<T> Not for Mask<T>
+where
+    T: private::Uint,
+{
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Mask::from_value(!self.value)
+    }
+}
+
+impl<T> From<Bits<T>> for Mask<T>
+where
+    T: private::Uint,
+{
+    fn from(bits: Bits<T>) -> Self {
+        Mask::from_value(bits.get_value())
+    }
+}
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Turns on every bit selected by `mask`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{Bits, Mask};
+    ///
+    /// let mut bits = Bits::from_value(0b0001_u8);
+    /// bits.apply_mask(Mask::from_value(0b0110));
+    /// assert_eq!(bits.get_value(), 0b0111);
+    /// ```
+    pub fn apply_mask(&mut self, mask: Mask<T>) {
+        self.value |= mask.value;
+    }
+
+    /// Turns off every bit selected by `mask`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{Bits, Mask};
+    ///
+    /// let mut bits = Bits::from_value(0b0111_u8);
+    /// bits.clear_mask(Mask::from_value(0b0110));
+    /// assert_eq!(bits.get_value(), 0b0001);
+    /// ```
+    pub fn clear_mask(&mut self, mask: Mask<T>) {
+        self.value &= !mask.value;
+    }
+
+    /// Flips every bit selected by `mask`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{Bits, Mask};
+    ///
+    /// let mut bits = Bits::from_value(0b0011_u8);
+    /// bits.toggle_mask(Mask::from_value(0b0110));
+    /// assert_eq!(bits.get_value(), 0b0101);
+    /// ```
+    pub fn toggle_mask(&mut self, mask: Mask<T>) {
+        self.value ^= mask.value;
+    }
+
+    /// Compares only the bits selected by `mask` with the same bits of `expected`.
+    ///
+    /// Bits outside of the mask are ignored, both in the stored value and in `expected`.
+    ///
+    /// # Parameters
+    ///
+    /// - `mask`: The bits to compare.
+    /// - `expected`: The value the selected bits must match.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if every selected bit of the stored value equals the same bit of `expected`.
+    /// - `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{Bits, Mask};
+    ///
+    /// let state = Mask::from_value(0b0000_1111_u8);
+    /// let bits = Bits::from_value(0b1010_0101_u8);
+    /// assert!(bits.masked_eq(state, 0b0101));
+    /// assert!(!bits.masked_eq(state, 0b0111));
+    /// ```
+    pub fn masked_eq(&self, mask: Mask<T>, expected: T) -> bool {
+        self.value & mask.value == expected & mask.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_masks() {
+        assert_eq!(Mask::<u16>::from_bits([0, 15, 16]).value(), 0x8001);
+        assert_eq!(Mask::<u16>::from_range(8..).unwrap().value(), 0xFF00);
+        assert!(Mask::<u16>::from_range(8..17).is_err());
+        assert_eq!(
+            (Mask::from_value(0b01_u8) | Mask::from_value(0b10)).value(),
+            0b11
+        );
+        assert_eq!((!Mask::from_value(0x0F_u8)).value(), 0xF0);
+        assert_eq!(Mask::from(Bits::from_value(7_u32)).value(), 7);
+    }
+
+    #[test]
+    fn apply_masks() {
+        let mask = Mask::from_value(0xF0_u8);
+        let mut bits = Bits::from_value(0x3C_u8);
+
+        bits.toggle_mask(mask);
+        assert_eq!(bits.get_value(), 0xCC);
+        assert!(bits.masked_eq(mask, 0xC0));
+        assert!(bits.masked_eq(mask, 0xCF));
+        bits.clear_mask(mask);
+        assert_eq!(bits.get_value(), 0x0C);
+        bits.apply_mask(mask);
+        assert_eq!(bits.get_value(), 0xFC);
+    }
+}
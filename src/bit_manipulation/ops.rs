@@ -0,0 +1,220 @@
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+
+use crate::bit_manipulation::Bits;
+use crate::private;
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Returns the bits that are set in `self`, in `other`, or in both.
+    ///
+    /// This is equivalent to the `|` operator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let read = Bits::from_value(0b001_u8);
+    /// let write = Bits::from_value(0b010_u8);
+    /// assert_eq!(read.union(&write).get_value(), 0b011);
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        Bits::from_value(self.value | other.value)
+    }
+
+    /// Returns the bits that are set in both `self` and `other`.
+    ///
+    /// This is equivalent to the `&` operator.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Bits::from_value(self.value & other.value)
+    }
+
+    /// Returns the bits that are set in `self` but not in `other`.
+    ///
+    /// This is equivalent to the `-` operator.
+    pub fn difference(&self, other: &Self) -> Self {
+        Bits::from_value(self.value & !other.value)
+    }
+
+    /// Returns the bits that are set in exactly one of `self` and `other`.
+    ///
+    /// This is equivalent to the `^` operator.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        Bits::from_value(self.value ^ other.value)
+    }
+
+    /// Returns the bits that are not set in `self`.
+    ///
+    /// This is equivalent to the `!` operator.
+    pub fn complement(&self) -> Self {
+        Bits::from_value(!self.value)
+    }
+
+    /// Returns `true` if every bit set in `self` is also set in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let read = Bits::from_value(0b001_u8);
+    /// let read_write = Bits::from_value(0b011_u8);
+    /// assert!(read.is_subset(&read_write));
+    /// assert!(read_write.is_superset(&read));
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.value & other.value == self.value
+    }
+
+    /// Returns `true` if every bit set in `other` is also set in `self`.
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` and `other` have no set bit in common.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.value & other.value == T::ZERO
+    }
+
+    /// Checks if all the bits at the given positions are set.
+    ///
+    /// An out of range position is never set, so it makes the method return `false`. An empty
+    /// list of positions is trivially contained.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to check, such as an array, a range or an iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0110_u8);
+    /// assert!(bits.contains_all([1, 2]));
+    /// assert!(bits.contains_all(1..3));
+    /// assert!(!bits.contains_all([1, 3]));
+    /// assert!(bits.contains_any([1, 3]));
+    /// ```
+    pub fn contains_all<I>(&self, bits: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Into<u8>,
+    {
+        bits.into_iter().all(|bit| self.is_bit_on(bit.into()))
+    }
+
+    /// Checks if at least one of the bits at the given positions is set.
+    ///
+    /// Out of range positions are never set. An empty list of positions is never contained.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The positions of the bits to check, such as an array, a range or an iterator.
+    pub fn contains_any<I>(&self, bits: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Into<u8>,
+    {
+        bits.into_iter().any(|bit| self.is_bit_on(bit.into()))
+    }
+}
+
+macro_rules! _impl_set_operator {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $set_method:ident) => {
+        impl<T> $op for Bits<T>
+        where
+            T: private::Uint,
+        {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                self.$set_method(&rhs)
+            }
+        }
+
+        impl<T> $op_assign for Bits<T>
+        where
+            T: private::Uint,
+        {
+            fn $method_assign(&mut self, rhs: Self) {
+                *self = self.$set_method(&rhs);
+            }
+        }
+    };
+}
+
+_impl_set_operator!(BitOr, bitor, BitOrAssign, bitor_assign, union);
+_impl_set_operator!(BitAnd, bitand, BitAndAssign, bitand_assign, intersection);
+_impl_set_operator!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference
+);
+_impl_set_operator!(Sub, sub, SubAssign, sub_assign, difference);
+
+impl<T> Not for Bits<T>
+where
+    T: private::Uint,
+{
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_operators() {
+        let a = Bits::from_value(0b1100_u8);
+        let b = Bits::from_value(0b1010_u8);
+
+        assert_eq!((a | b).get_value(), 0b1110);
+        assert_eq!((a & b).get_value(), 0b1000);
+        assert_eq!((a ^ b).get_value(), 0b0110);
+        assert_eq!((a - b).get_value(), 0b0100);
+        assert_eq!((!a).get_value(), 0b1111_0011);
+
+        let mut c = a;
+        c |= b;
+        assert_eq!(c.get_value(), 0b1110);
+        c &= a;
+        assert_eq!(c.get_value(), 0b1100);
+        c ^= b;
+        assert_eq!(c.get_value(), 0b0110);
+        c -= b;
+        assert_eq!(c.get_value(), 0b0100);
+    }
+
+    #[test]
+    fn predicates() {
+        let a = Bits::from_value(0b0100_u16);
+        let b = Bits::from_value(0b1100_u16);
+        let empty = Bits::<u16>::new();
+
+        assert!(a.is_subset(&b));
+        assert!(!b.is_subset(&a));
+        assert!(b.is_superset(&a));
+        assert!(empty.is_subset(&a));
+        assert!(a.is_disjoint(&Bits::from_value(0b0011)));
+        assert!(!a.is_disjoint(&b));
+
+        assert!(b.contains_all([2, 3]));
+        assert!(!b.contains_all(vec![2_u8, 3, 40]));
+        assert!(b.contains_all([0_u8; 0]));
+        assert!(b.contains_any([0, 3].iter().copied()));
+        assert!(!b.contains_any(0..2));
+        assert!(!b.contains_any([40, 255]));
+        assert!(!b.contains_any([0_u8; 0]));
+    }
+}
@@ -0,0 +1,265 @@
+use core::ops::{Bound, Range, RangeBounds};
+
+use crate::bit_manipulation::{BitError, Bits};
+use crate::private;
+
+impl<T> Bits<T>
+where
+    T: private::Uint,
+{
+    /// Turns on every bit of a range.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The positions to turn on, such as `2..5`, `4..=7` or `3..`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the bits were successfully turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let mut bits: Bits<u8> = Bits::new();
+    /// bits.set_range(2..5).unwrap();
+    /// assert_eq!(bits.get_value(), 0b0001_1100);
+    /// bits.set_range(6..).unwrap();
+    /// assert_eq!(bits.get_value(), 0b1101_1100);
+    /// ```
+    pub fn set_range(&mut self, range: impl RangeBounds<u8>) -> Result<(), BitError> {
+        self.value |= Self::range_mask(range)?;
+        Ok(())
+    }
+
+    /// Turns off every bit of a range.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The positions to turn off.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the bits were successfully turned off.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let mut bits = Bits::from_value(0xFF_u8);
+    /// bits.clear_range(..4).unwrap();
+    /// assert_eq!(bits.get_value(), 0xF0);
+    /// ```
+    pub fn clear_range(&mut self, range: impl RangeBounds<u8>) -> Result<(), BitError> {
+        self.value &= !Self::range_mask(range)?;
+        Ok(())
+    }
+
+    /// Flips every bit of a range.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The positions to flip.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the bits were successfully flipped.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let mut bits = Bits::from_value(0b1010_u8);
+    /// bits.toggle_range(0..=3).unwrap();
+    /// assert_eq!(bits.get_value(), 0b0101);
+    /// ```
+    pub fn toggle_range(&mut self, range: impl RangeBounds<u8>) -> Result<(), BitError> {
+        self.value ^= Self::range_mask(range)?;
+        Ok(())
+    }
+
+    /// Counts the bits turned on in a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(count)` holding the number of bits turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1011_0110_u8);
+    /// assert_eq!(bits.count_range(0..4), Ok(2));
+    /// assert_eq!(bits.count_range(..), Ok(5));
+    /// ```
+    pub fn count_range(&self, range: impl RangeBounds<u8>) -> Result<u32, BitError> {
+        Ok((self.value & Self::range_mask(range)?).count_ones())
+    }
+
+    /// Returns `Ok(true)` if at least one bit of the range is turned on.
+    ///
+    /// An empty range holds no bit, so it yields `Ok(false)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0100_0000_u8);
+    /// assert_eq!(bits.any_in_range(4..8), Ok(true));
+    /// assert_eq!(bits.any_in_range(..4), Ok(false));
+    /// ```
+    pub fn any_in_range(&self, range: impl RangeBounds<u8>) -> Result<bool, BitError> {
+        Ok(self.value & Self::range_mask(range)? != T::ZERO)
+    }
+
+    /// Returns `Ok(true)` if every bit of the range is turned on.
+    ///
+    /// An empty range holds no bit, so it yields `Ok(true)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0111_0000_u8);
+    /// assert_eq!(bits.all_in_range(4..7), Ok(true));
+    /// assert_eq!(bits.all_in_range(4..), Ok(false));
+    /// ```
+    pub fn all_in_range(&self, range: impl RangeBounds<u8>) -> Result<bool, BitError> {
+        let mask = Self::range_mask(range)?;
+        Ok(self.value & mask == mask)
+    }
+
+    /// Returns the lowest position turned on in a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(bit))` holding the lowest position turned on.
+    /// - `Ok(None)` if no bit of the range is turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1001_0010_u8);
+    /// assert_eq!(bits.first_set_in_range(2..), Ok(Some(4)));
+    /// assert_eq!(bits.first_set_in_range(5..7), Ok(None));
+    /// ```
+    pub fn first_set_in_range(&self, range: impl RangeBounds<u8>) -> Result<Option<u8>, BitError> {
+        let masked = self.value & Self::range_mask(range)?;
+        if masked == T::ZERO {
+            return Ok(None);
+        }
+        Ok(Some(masked.trailing_zeros() as u8))
+    }
+
+    /// Returns the highest position turned on in a range.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(bit))` holding the highest position turned on.
+    /// - `Ok(None)` if no bit of the range is turned on.
+    /// - `Err(BitError::InvalidRange)` if the range is reversed or ends beyond the bit width of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b1001_0010_u8);
+    /// assert_eq!(bits.last_set_in_range(..7), Ok(Some(4)));
+    /// assert_eq!(bits.last_set_in_range(..), Ok(Some(7)));
+    /// ```
+    pub fn last_set_in_range(&self, range: impl RangeBounds<u8>) -> Result<Option<u8>, BitError> {
+        let masked = self.value & Self::range_mask(range)?;
+        if masked == T::ZERO {
+            return Ok(None);
+        }
+        Ok(Some(T::in_memory_size() - 1 - masked.leading_zeros() as u8))
+    }
+
+    /// Returns the mask of the bits of `range`, shifted to their positions.
+    pub(super) fn range_mask(range: impl RangeBounds<u8>) -> Result<T, BitError> {
+        let width = T::in_memory_size() as usize;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start as usize,
+            Bound::Excluded(&start) => start as usize + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end as usize + 1,
+            Bound::Excluded(&end) => end as usize,
+            Bound::Unbounded => width,
+        };
+        if start > end || end > width {
+            return Err(BitError::InvalidRange { start, end, width });
+        }
+        let range = Range {
+            start: start as u8,
+            end: end as u8,
+        };
+        if range.is_empty() {
+            return Ok(T::ZERO);
+        }
+        Ok(Self::field_mask(&range)? << range.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_bounds() {
+        let mut bits: Bits<u16> = Bits::new();
+
+        bits.set_range(..).unwrap();
+        assert_eq!(bits.get_value(), u16::MAX);
+        bits.clear_range(4..=11).unwrap();
+        assert_eq!(bits.get_value(), 0xF00F);
+        bits.toggle_range((Bound::Excluded(0), Bound::Included(3)))
+            .unwrap();
+        assert_eq!(bits.get_value(), 0xF001);
+        bits.set_range(16..16).unwrap();
+        assert_eq!(bits.get_value(), 0xF001);
+
+        assert_eq!(
+            bits.set_range(..=16),
+            Err(BitError::InvalidRange {
+                start: 0,
+                end: 17,
+                width: 16
+            })
+        );
+        assert!(bits
+            .clear_range((Bound::Excluded(255), Bound::Unbounded))
+            .is_err());
+        assert_eq!(bits.get_value(), 0xF001);
+    }
+
+    #[test]
+    fn range_queries() {
+        let bits = Bits::from_value(0b0110_1000_u128 | 1 << 127);
+
+        assert_eq!(bits.count_range(..), Ok(4));
+        assert_eq!(bits.count_range(0..8), Ok(3));
+        assert_eq!(bits.any_in_range(0..3), Ok(false));
+        assert_eq!(bits.all_in_range(5..7), Ok(true));
+        assert_eq!(bits.all_in_range(3..3), Ok(true));
+        assert_eq!(bits.first_set_in_range(..), Ok(Some(3)));
+        assert_eq!(bits.first_set_in_range(7..), Ok(Some(127)));
+        assert_eq!(bits.last_set_in_range(..127), Ok(Some(6)));
+        assert_eq!(bits.last_set_in_range(7..127), Ok(None));
+    }
+}
@@ -0,0 +1,279 @@
+//! Serde representations of [`Bits`], available with the `serde` feature.
+//!
+//! By default a [`Bits`] value is serialized as its raw integer. The [`positions`] module and
+//! the [`FlagTable::serialize_names`] / [`FlagTable::deserialize_names`] pair give two other
+//! representations, selected with `#[serde(with = "...")]` or
+//! `#[serde(serialize_with = "...", deserialize_with = "...")]`.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use alloc::{string::String, vec::Vec};
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::bit_manipulation::{BitError, Bits, FlagTable};
+use crate::private;
+
+impl<T> Serialize for Bits<T>
+where
+    T: private::Uint + Serialize,
+{
+    /// Serializes the value as its raw integer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::Bits;
+    ///
+    /// let bits = Bits::from_value(0b0101_u8);
+    /// assert_eq!(serde_json::to_string(&bits)?, "5");
+    /// assert_eq!(serde_json::from_str::<Bits<u8>>("5")?, bits);
+    /// assert!(serde_json::from_str::<Bits<u8>>("256").is_err());
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Bits<T>
+where
+    T: private::Uint + Deserialize<'de>,
+{
+    /// Deserializes the value from its raw integer, which must fit in `T`.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Bits::from_value)
+    }
+}
+
+/// Serializes a [`Bits`] value as the ascending list of its set positions.
+///
+/// Deserialization rejects positions greater than or equal to the bit width of `T`.
+///
+/// # Example
+///
+/// ```
+/// use bit_manipulation::bit_manipulation::Bits;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Options {
+///     #[serde(with = "bit_manipulation::bit_manipulation::serialization::positions")]
+///     enabled: Bits<u16>,
+/// }
+///
+/// let options = Options { enabled: Bits::from_value(0b1001) };
+/// assert_eq!(serde_json::to_string(&options)?, r#"{"enabled":[0,3]}"#);
+/// assert!(serde_json::from_str::<Options>(r#"{"enabled":[16]}"#).is_err());
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub mod positions {
+    use super::*;
+
+    /// Serializes the set positions of `bits`, in ascending order.
+    pub fn serialize<T, S>(bits: &Bits<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: private::Uint,
+        S: Serializer,
+    {
+        serializer.collect_seq(bits.iter_ones())
+    }
+
+    /// Deserializes a list of set positions, in any order.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Bits<T>, D::Error>
+    where
+        T: private::Uint,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PositionsVisitor(PhantomData))
+    }
+
+    struct PositionsVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for PositionsVisitor<T>
+    where
+        T: private::Uint,
+    {
+        type Value = Bits<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "a list of bit positions lower than {}",
+                T::in_memory_size()
+            )
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Bits<T>, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let width = T::in_memory_size() as usize;
+            let mut bits = Bits::new();
+            while let Some(bit) = seq.next_element::<usize>()? {
+                if bit >= width {
+                    return Err(de::Error::custom(BitError::OutOfRange { bit, width }));
+                }
+                bits.set_bit(bit as u8);
+            }
+            Ok(bits)
+        }
+    }
+}
+
+impl<T> FlagTable<'_, T>
+where
+    T: private::Uint,
+{
+    /// Serializes `bits` as a list of flag names.
+    ///
+    /// The names are the terms of [`FlagTable::format`], so bits without a name are written as
+    /// hexadecimal strings. Meant to be called from a `serialize_with` function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bit_manipulation::bit_manipulation::{Bits, FlagTable};
+    /// use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    ///
+    /// const PERMISSIONS: FlagTable<'static, u8> = FlagTable::new(&[
+    ///     ("READ", Bits::from_value(0b001)),
+    ///     ("WRITE", Bits::from_value(0b010)),
+    /// ]);
+    ///
+    /// fn serialize<S: Serializer>(bits: &Bits<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+    ///     PERMISSIONS.serialize_names(bits, serializer)
+    /// }
+    ///
+    /// fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Bits<u8>, D::Error> {
+    ///     PERMISSIONS.deserialize_names(deserializer)
+    /// }
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct File {
+    ///     #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+    ///     permissions: Bits<u8>,
+    /// }
+    ///
+    /// let file = File { permissions: Bits::from_value(0b1000_0011) };
+    /// let json = serde_json::to_string(&file)?;
+    /// assert_eq!(json, r#"{"permissions":["READ","WRITE","0x80"]}"#);
+    /// assert_eq!(serde_json::from_str::<File>(&json)?.permissions, file.permissions);
+    /// assert!(serde_json::from_str::<File>(r#"{"permissions":["EXEC"]}"#).is_err());
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn serialize_names<S>(&self, bits: &Bits<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.names(*bits))
+    }
+
+    /// Deserializes a list of flag names, written as [`FlagTable::serialize_names`] does.
+    ///
+    /// Each element must be a name of the table, looked up with [`FlagTable::get`], or a
+    /// hexadecimal number with a `0x` prefix standing for bits without a name. Anything else,
+    /// including flag expressions such as `"!READ"`, is rejected with [`BitError::UnknownFlag`],
+    /// and numbers that do not fit in `T` with the matching [`BitError`].
+    pub fn deserialize_names<'de, D>(&self, deserializer: D) -> Result<Bits<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .try_fold(Bits::new(), |bits, name| {
+                self.name_or_number(name).map(|flag| bits | flag)
+            })
+            .map_err(de::Error::custom)
+    }
+
+    /// Returns the bits of a single term written by [`FlagTable::serialize_names`].
+    fn name_or_number(&self, name: String) -> Result<Bits<T>, BitError> {
+        if let Some(bits) = self.get(&name) {
+            return Ok(bits);
+        }
+        if name.starts_with("0x") {
+            return name.parse();
+        }
+        Err(BitError::UnknownFlag { name, column: 1 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_and_positions() {
+        let bits = Bits::from_value(-2_i8);
+        assert_eq!(serde_json::to_string(&bits).unwrap(), "-2");
+        assert_eq!(serde_json::from_str::<Bits<i8>>("-2").unwrap(), bits);
+
+        let mut json = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut json);
+        positions::serialize(&Bits::from_value(0x8001_u16), &mut serializer).unwrap();
+        assert_eq!(json, b"[0,15]");
+
+        let mut deserializer = serde_json::Deserializer::from_str("[15,0,0]");
+        let bits: Bits<u16> = positions::deserialize(&mut deserializer).unwrap();
+        assert_eq!(bits.get_value(), 0x8001);
+
+        let mut deserializer = serde_json::Deserializer::from_str("[1,300]");
+        let error = positions::deserialize::<u16, _>(&mut deserializer).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("bit 300 is out of range for a width of 16 bits"));
+    }
+    crate::bit_options! {
+        struct Permissions {
+            const READ = 0;
+            const WRITE = 1;
+            const EXEC = 2;
+        }
+    }
+
+    #[test]
+    fn names() {
+        let permissions = Permissions::READ | Permissions::EXEC;
+        let json = serde_json::to_string(&permissions).unwrap();
+        assert_eq!(json, r#"["READ","EXEC"]"#);
+        assert_eq!(
+            serde_json::from_str::<Permissions>(&json).unwrap(),
+            permissions
+        );
+        assert_eq!(
+            serde_json::from_str::<Permissions>("[]").unwrap(),
+            Permissions::empty()
+        );
+
+        let error = serde_json::from_str::<Permissions>(r#"["READ","ROOT"]"#).unwrap_err();
+        assert!(error.to_string().starts_with("unknown flag `ROOT`"));
+        assert!(serde_json::from_str::<Permissions>(r#"["0x100"]"#).is_err());
+
+        assert_eq!(
+            serde_json::from_str::<Permissions>(r#"["0x80","WRITE"]"#)
+                .unwrap()
+                .bits()
+                .get_value(),
+            0x82
+        );
+        for expression in [
+            r#"["!READ"]"#,
+            r#"["READ | WRITE & EXEC"]"#,
+            r#"["(READ)"]"#,
+        ] {
+            let error = serde_json::from_str::<Permissions>(expression).unwrap_err();
+            assert!(error.to_string().starts_with("unknown flag"), "{error}");
+        }
+        assert!(serde_json::from_str::<Permissions>(r#"["0b1"]"#).is_err());
+        assert!(serde_json::from_str::<Permissions>(r#"["0x1 | 0x2"]"#).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use core::iter::FusedIterator;
+
+/// The number of bits of a `u64` word, the storage unit of [`BitArray`](super::BitArray) and
+/// `BitVec`.
+pub(crate) const WORD_BITS: usize = u64::BITS as usize;
+
+/// Checks if the bit at position `bit` is set in `words`, which must hold that position.
+pub(crate) fn word_bit(words: &[u64], bit: usize) -> bool {
+    words[bit / WORD_BITS] & (1 << (bit % WORD_BITS)) != 0
+}
+
+/// A lazily computed view over the state of every bit of a [`BitArray`](super::BitArray) or a
+/// `BitVec`.
+///
+/// The view yields one boolean per addressable bit, from position 0 to `len - 1`, and borrows
+/// the words it reads.
+#[derive(Debug, Clone)]
+pub struct WordView<'a> {
+    words: &'a [u64],
+    front: usize,
+    back: usize,
+}
+
+impl<'a> WordView<'a> {
+    pub(crate) fn new(words: &'a [u64], len: usize) -> Self {
+        WordView {
+            words,
+            front: 0,
+            back: len,
+        }
+    }
+}
+
+impl Iterator for WordView<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        let state = word_bit(self.words, self.front);
+        self.front += 1;
+        Some(state)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for WordView<'_> {
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(word_bit(self.words, self.back))
+    }
+}
+
+impl ExactSizeIterator for WordView<'_> {}
+
+impl FusedIterator for WordView<'_> {}
+
+/// An iterator telling, for each requested position, whether its bit is set in a
+/// [`BitArray`](super::BitArray) or a `BitVec`.
+///
+/// Out of range positions are reported as not set. This struct is created by the
+/// `are_bits_on` methods.
+#[derive(Debug, Clone)]
+pub struct WordBitsOn<'a, I> {
+    words: &'a [u64],
+    len: usize,
+    positions: I,
+}
+
+impl<'a, I> WordBitsOn<'a, I> {
+    pub(crate) fn new(words: &'a [u64], len: usize, positions: I) -> Self {
+        WordBitsOn {
+            words,
+            len,
+            positions,
+        }
+    }
+
+    fn state(&self, bit: usize) -> bool {
+        bit < self.len && word_bit(self.words, bit)
+    }
+}
+
+impl<I> Iterator for WordBitsOn<'_, I>
+where
+    I: Iterator,
+    I::Item: Into<usize>,
+{
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let bit = self.positions.next()?;
+        Some(self.state(bit.into()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for WordBitsOn<'_, I>
+where
+    I: DoubleEndedIterator,
+    I::Item: Into<usize>,
+{
+    fn next_back(&mut self) -> Option<bool> {
+        let bit = self.positions.next_back()?;
+        Some(self.state(bit.into()))
+    }
+}
+
+impl<I> ExactSizeIterator for WordBitsOn<'_, I>
+where
+    I: ExactSizeIterator,
+    I::Item: Into<usize>,
+{
+}
+
+impl<I> FusedIterator for WordBitsOn<'_, I>
+where
+    I: FusedIterator,
+    I::Item: Into<usize>,
+{
+}
+
+/// An iterator over the positions of the set bits of a [`BitArray`](super::BitArray).
+///
+/// The iterator holds a copy of the words it was created from and skips empty words, so its
+/// cost depends on the number of words and set bits rather than on the number of positions.
+/// This struct is created by [`BitArray::iter_ones`](super::BitArray::iter_ones).
+#[derive(Debug, Clone)]
+pub struct ArrayIterOnes<const W: usize> {
+    remaining: [u64; W],
+    front: usize,
+    back: usize,
+}
+
+impl<const W: usize> ArrayIterOnes<W> {
+    pub(crate) fn new(remaining: [u64; W]) -> Self {
+        ArrayIterOnes {
+            remaining,
+            front: 0,
+            back: W,
+        }
+    }
+}
+
+impl<const W: usize> Iterator for ArrayIterOnes<W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.front < self.back {
+            let word = &mut self.remaining[self.front];
+            if *word != 0 {
+                let bit = word.trailing_zeros() as usize;
+                *word &= *word - 1;
+                return Some(self.front * WORD_BITS + bit);
+            }
+            self.front += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining[self.front..self.back]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+        (len, Some(len))
+    }
+}
+
+impl<const W: usize> DoubleEndedIterator for ArrayIterOnes<W> {
+    fn next_back(&mut self) -> Option<usize> {
+        while self.front < self.back {
+            let word = &mut self.remaining[self.back - 1];
+            if *word != 0 {
+                let bit = WORD_BITS - 1 - word.leading_zeros() as usize;
+                *word &= !(1 << bit);
+                return Some((self.back - 1) * WORD_BITS + bit);
+            }
+            self.back -= 1;
+        }
+        None
+    }
+}
+
+impl<const W: usize> ExactSizeIterator for ArrayIterOnes<W> {}
+
+impl<const W: usize> FusedIterator for ArrayIterOnes<W> {}
+
+/// An iterator over the positions of the cleared bits of a [`BitArray`](super::BitArray).
+///
+/// This struct is created by [`BitArray::iter_zeros`](super::BitArray::iter_zeros).
+#[derive(Debug, Clone)]
+pub struct ArrayIterZeros<const W: usize>(pub(crate) ArrayIterOnes<W>);
+
+impl<const W: usize> Iterator for ArrayIterZeros<W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<const W: usize> DoubleEndedIterator for ArrayIterZeros<W> {
+    fn next_back(&mut self) -> Option<usize> {
+        self.0.next_back()
+    }
+}
+
+impl<const W: usize> ExactSizeIterator for ArrayIterZeros<W> {}
+
+impl<const W: usize> FusedIterator for ArrayIterZeros<W> {}
@@ -1,54 +1,305 @@
+// Unit tests always link `std` for the test harness and their helpers, so the `no_std` code
+// paths are tested with `cargo test --no-default-features [--features alloc]`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 mod private {
-    pub use std::ops::*;
+    pub use core::ops::*;
 
+    /// An integer-like word whose bits can be stored in a [`Bits`](crate::bit_manipulation::Bits).
+    ///
+    /// The trait is implemented for every primitive integer: `u8` to `u128`, `usize`, and the
+    /// signed `i8` to `i128` and `isize`, whose values are handled as raw bit patterns. Bit
+    /// `n - 1` of a signed integer is its sign bit, so `Bits::<i8>::from_value(-1)` has every bit
+    /// set. It is exported as [`BitStorage`](crate::BitStorage) and can be implemented by other
+    /// crates for their own word types, such as wrapper newtypes, as long as the operators behave
+    /// like those of a fixed-width integer.
+    ///
+    /// Bit positions are `u8`, so a word type holds at most 255 bits; wider types, such as
+    /// 256-bit SIMD vectors, cannot implement the trait.
     pub trait Uint:
-        std::fmt::Display
-        + BitAnd<Self, Output = Self>
+        BitAnd<Self, Output = Self>
         + BitOr<Self, Output = Self>
+        + BitXor<Self, Output = Self>
         + BitAndAssign<Self>
         + BitOrAssign<Self>
+        + BitXorAssign<Self>
         + Not<Output = Self>
-        + std::cmp::PartialOrd<Self>
+        + Shl<u8, Output = Self>
+        + Shr<u8, Output = Self>
+        + core::cmp::Eq
+        + core::cmp::Ord
+        + core::hash::Hash
         + Copy
-        + From<u8>
     {
-        fn max_value() -> Self;
-        fn in_memory_size() -> u8;
+        /// The bit width of the type, usable in constant expressions. It is at most 255.
+        const BITS: u8;
+
+        /// The word with every bit cleared.
+        const ZERO: Self;
+
+        /// The word with every bit set.
+        const ONES: Self;
+
+        /// Returns the bit width of the type, see [`Uint::BITS`].
+        fn in_memory_size() -> u8 {
+            Self::BITS
+        }
+
+        /// Returns the word with only the bit at position `bit` set.
         fn convert(bit: u8) -> Self;
+
+        /// Returns the number of bits set in the word.
+        fn count_ones(self) -> u32;
+
+        /// Returns the number of cleared bits above the highest set bit.
+        fn leading_zeros(self) -> u32;
+
+        /// Returns the number of cleared bits below the lowest set bit.
+        fn trailing_zeros(self) -> u32;
+
+        /// The bytes of the word, `BITS / 8` of them.
+        type Bytes: AsRef<[u8]> + AsMut<[u8]> + Copy;
+
+        /// Returns the bytes of the word, least significant byte first.
+        fn to_le_bytes(self) -> Self::Bytes;
+
+        /// Builds a word from its bytes, least significant byte first.
+        fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+        /// Rotates the bits left by `n` positions, wrapping the bits shifted out to the right.
+        ///
+        /// The default implementation moves one bit at a time; the primitive integers use their
+        /// native instruction.
+        fn rotate_left(self, n: u32) -> Self {
+            let width = Self::BITS as u32;
+            let n = n % width;
+            (0..Self::BITS)
+                .filter(|&bit| self & Self::convert(bit) != Self::ZERO)
+                .fold(Self::ZERO, |word, bit| {
+                    word | Self::convert(((bit as u32 + n) % width) as u8)
+                })
+        }
+
+        /// Rotates the bits right by `n` positions, wrapping the bits shifted out to the left.
+        fn rotate_right(self, n: u32) -> Self {
+            let n = n % Self::BITS as u32;
+            self.rotate_left(Self::BITS as u32 - n)
+        }
+
+        /// Reverses the order of the bits: bit 0 becomes bit `BITS - 1` and so on.
+        fn reverse_bits(self) -> Self {
+            (0..Self::BITS)
+                .filter(|&bit| self & Self::convert(bit) != Self::ZERO)
+                .fold(Self::ZERO, |word, bit| {
+                    word | Self::convert(Self::BITS - 1 - bit)
+                })
+        }
+
+        /// Reverses the order of the bytes.
+        fn swap_bytes(self) -> Self {
+            let mut bytes = self.to_le_bytes();
+            bytes.as_mut().reverse();
+            Self::from_le_bytes(bytes)
+        }
     }
 }
 
 macro_rules! _impl_private {
-    ($($t:ty),+) => {
+    ($($t:ty => $non_zero:ty),+ $(,)?) => {
         $(
             impl $crate::private::Uint for $t {
-            fn max_value() -> Self {
-                <$t>::MAX
-            }
-            fn in_memory_size() -> u8 {
-                <$t>::BITS as u8
-            }
+            const BITS: u8 = <$t>::BITS as u8;
+            const ZERO: Self = 0;
+            const ONES: Self = !0;
+
             fn convert(bit: u8) -> Self {
                 1 << bit
             }
+            fn count_ones(self) -> u32 {
+                <$t>::count_ones(self)
+            }
+            fn leading_zeros(self) -> u32 {
+                <$t>::leading_zeros(self)
+            }
+            fn trailing_zeros(self) -> u32 {
+                <$t>::trailing_zeros(self)
+            }
+
+            type Bytes = [u8; core::mem::size_of::<$t>()];
+
+            fn to_le_bytes(self) -> Self::Bytes {
+                <$t>::to_le_bytes(self)
+            }
+            fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                <$t>::from_le_bytes(bytes)
+            }
+            fn rotate_left(self, n: u32) -> Self {
+                <$t>::rotate_left(self, n)
+            }
+            fn rotate_right(self, n: u32) -> Self {
+                <$t>::rotate_right(self, n)
+            }
+            fn reverse_bits(self) -> Self {
+                <$t>::reverse_bits(self)
+            }
+            fn swap_bytes(self) -> Self {
+                <$t>::swap_bytes(self)
+            }
+        }
+
+        impl $crate::bit_manipulation::Bits<$t> {
+            /// Returns a copy of the value with the bit at position `bit` set.
+            ///
+            /// Unlike [`Bits::set_bit`](crate::bit_manipulation::Bits::set_bit), this is a `const fn`,
+            /// so option masks can be built in `const` and `static` items. It is only available for
+            /// the primitive integers, as trait methods cannot be called in constant expressions.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `bit` is greater than or equal to the bit width of the integer, which fails
+            /// the build when the call is evaluated in a constant.
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use bit_manipulation::bit_manipulation::Bits;
+            ///
+            /// static MASK: Bits<u16> = Bits::<u16>::new().with_bit(3).with_bit(9);
+            /// const _: () = assert!(MASK.has_bit(9));
+            /// assert!(MASK.iter_ones().eq([3, 9]));
+            /// ```
+            #[track_caller]
+            pub const fn with_bit(self, bit: u8) -> Self {
+                assert!(bit < <$t>::BITS as u8, "bit position is out of range");
+                Self::from_value(self.get_value() | 1 << bit)
+            }
+
+            /// Checks if the bit at position `bit` is set.
+            ///
+            /// This is the `const fn` counterpart of
+            /// [`Bits::is_bit_on`](crate::bit_manipulation::Bits::is_bit_on), only available for the
+            /// primitive integers. Out of range positions are reported as not set.
+            pub const fn has_bit(&self, bit: u8) -> bool {
+                bit < <$t>::BITS as u8 && self.get_value() & 1 << bit != 0
+            }
+        }
+
+        impl From<$crate::bit_manipulation::Bits<$t>> for $t {
+            fn from(bits: $crate::bit_manipulation::Bits<$t>) -> Self {
+                bits.get_value()
+            }
+        }
+
+        impl From<$non_zero> for $crate::bit_manipulation::Bits<$t> {
+            fn from(value: $non_zero) -> Self {
+                $crate::bit_manipulation::Bits::from_value(value.get())
+            }
+        }
+
+        impl TryFrom<$crate::bit_manipulation::Bits<$t>> for $non_zero {
+            type Error = $crate::bit_manipulation::Bits<$t>;
+
+            /// Converts a `Bits` with at least one bit set, handing it back unchanged otherwise.
+            fn try_from(bits: $crate::bit_manipulation::Bits<$t>) -> Result<Self, Self::Error> {
+                <$non_zero>::new(bits.get_value()).ok_or(bits)
+            }
         }
     )+
     };
 }
 
-_impl_private![u8, u16, u32, u64, u128];
+_impl_private![
+    u8 => core::num::NonZeroU8,
+    u16 => core::num::NonZeroU16,
+    u32 => core::num::NonZeroU32,
+    u64 => core::num::NonZeroU64,
+    u128 => core::num::NonZeroU128,
+    usize => core::num::NonZeroUsize,
+    i8 => core::num::NonZeroI8,
+    i16 => core::num::NonZeroI16,
+    i32 => core::num::NonZeroI32,
+    i64 => core::num::NonZeroI64,
+    i128 => core::num::NonZeroI128,
+    isize => core::num::NonZeroIsize,
+];
+
+pub use private::Uint as BitStorage;
+
+mod macros;
+
+#[cfg(feature = "derive")]
+pub use bit_manipulation_derive::{bitfield, BitOption};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macros::{bit_width, has_duplicates, narrowest_width, Backing, Width};
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
 
 pub mod bit_manipulation {
     use crate::private;
 
-    #[derive(Default, Debug)]
+    mod atomic;
+    mod batch;
+    mod bit_array;
+    #[cfg(feature = "alloc")]
+    mod bit_vec;
+    mod bytes;
+    mod enum_set;
+    mod error;
+    #[cfg(feature = "std")]
+    mod event;
+    mod field;
+    mod flags;
+    mod fmt;
+    #[cfg(feature = "std")]
+    mod future;
+    mod intrinsics;
+    mod iter;
+    mod mask;
+    mod ops;
+    mod range;
+    #[cfg(feature = "serde")]
+    pub mod serialization;
+    mod words;
+
+    pub use atomic::{AtomicBits, AtomicStorage};
+    pub use batch::{BatchOutcome, BatchResult, Rejected};
+    pub use bit_array::{words_for, BitArray};
+    #[cfg(feature = "alloc")]
+    pub use bit_vec::BitVec;
+    pub use bytes::BitOrder;
+    pub use enum_set::{BitOption, EnumSet, EnumSetIter};
+    pub use error::BitError;
+    #[cfg(feature = "std")]
+    pub use event::EventBits;
+    pub use flags::FlagTable;
+    #[cfg(feature = "std")]
+    pub use future::{AsyncBits, WaitUntil};
+    pub use iter::{BitView, BitsOn, IterOnes, IterZeros};
+    pub use mask::Mask;
+    pub use words::{ArrayIterOnes, ArrayIterZeros, WordBitsOn, WordView};
+
+    /// A set of bits stored in a single word of type `T`.
+    ///
+    /// `T` is any [`BitStorage`](crate::BitStorage) type: a primitive integer holds up to 128
+    /// bits, and a custom word type can hold up to 255, as bit positions are `u8`.
+    ///
+    /// The integer is the only state kept by `Bits`, so the struct is `Copy` and has the same
+    /// layout as `T` (`#[repr(transparent)]`). Views over individual bits, such as [`Bits::get_all_bits`],
+    /// are computed lazily from it. Comparing, ordering and hashing two `Bits` compares,
+    /// orders and hashes their values.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
     pub struct Bits<T>
     where
         T: private::Uint,
     {
         value: T,
-        activated_bits: Vec<bool>,
-        size: u8,
     }
 
     impl<T> Bits<T>
@@ -58,9 +309,8 @@ pub mod bit_manipulation {
         /// Creates a new instance of the `Bits` struct with an initial value of zero.
         ///
         /// This method constructs a new instance of the `Bits` struct, initializing the stored value
-        /// to zero and determining the size of the value based on the memory size of type `T`. It also
-        /// initializes a vector of boolean flags (`activated_bits`) to track the activation status of
-        /// individual bits within the stored value.
+        /// to zero. No allocation is performed: the size of the value is derived from the memory size
+        /// of type `T` whenever it is needed.
         ///
         /// # Returns
         ///
@@ -75,13 +325,38 @@ pub mod bit_manipulation {
         ///
         /// This example demonstrates how to use the `new` method to create a new instance of the `Bits`
         /// struct with default values.
+        pub const fn new() -> Self {
+            Bits { value: T::ZERO }
+        }
 
-        pub fn new() -> Self {
-            Bits {
-                value: T::from(0),
-                size: T::in_memory_size(),
-                activated_bits: vec![false; T::in_memory_size() as usize],
-            }
+        /// Creates a new instance of the `Bits` struct holding an existing value.
+        ///
+        /// This method wraps a value that is already known, for example flags read from a database
+        /// column, so that its bits can be checked and manipulated. Every view over the bits, such as
+        /// [`Bits::get_all_bits`], is derived from that value.
+        ///
+        /// # Parameters
+        ///
+        /// - `value`: The initial value of the stored unsigned integer.
+        ///
+        /// # Returns
+        ///
+        /// A new instance of the `Bits` struct with the initial value set to `value`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let bits = Bits::from_value(0b1010_u8);
+        /// assert!(bits.is_bit_on(1));
+        /// assert!(bits.is_bit_on(3));
+        /// assert!(bits.get_all_bits().eq([false, true, false, true, false, false, false, false]));
+        /// ```
+        ///
+        /// This example demonstrates how to use the `from_value` method to load an existing value.
+        pub const fn from_value(value: T) -> Self {
+            Bits { value }
         }
 
         /// Checks if a specific bit is set (activated) within the value stored in the struct instance.
@@ -91,6 +366,10 @@ pub mod bit_manipulation {
         /// bit position is greater than or equal to the size of the value, indicating it's out of range,
         /// the method returns `false`.
         ///
+        /// `is_bit_on` works with any [`BitStorage`](crate::BitStorage) type, whose operators cannot
+        /// be called in a constant expression on stable Rust, so it is not a `const fn`. For the
+        /// primitive integers, [`Bits::has_bit`] is its `const fn` counterpart and behaves the same.
+        ///
         /// # Parameters
         ///
         /// - `bit`: The position of the bit to check within the value, ranging from 0 to `n - 1`(for example for u8 where n = 8) it would have to be between 0 and 7.
@@ -98,7 +377,7 @@ pub mod bit_manipulation {
         /// # Returns
         ///
         /// - `true` if the specified bit is set (activated).
-        /// - `false` if the provided bit position is out of range (greater than or equal to `self.size`)
+        /// - `false` if the provided bit position is out of range (greater than or equal to the bit width of `T`)
         ///   or if the bit is not set.
         /// # Example
         ///
@@ -111,53 +390,49 @@ pub mod bit_manipulation {
         ///
         /// This example demonstrates how to use the `new` method to create a new instance of the `Bits`
         /// struct with default values.
-
         pub fn is_bit_on(&self, bit: u8) -> bool {
-            if bit >= self.size {
+            if bit >= T::in_memory_size() {
                 return false;
             }
 
-            self.value & T::convert(bit) > T::from(0)
+            self.value & T::convert(bit) != T::ZERO
         }
 
         /// Checks if specific bits are set (activated) within the value stored in the struct instance.
         ///
-        /// This method examines whether the bits at the positions specified in the provided vector `bits`
-        /// are set (activated) within the binary representation of the value stored in the struct instance.
-        /// It returns a vector of boolean values indicating whether each specified bit is set (`true`) or
-        /// not (`false`). If a provided bit position is out of range (greater than or equal to `self.size`),
-        /// it is considered as not activated.
+        /// This method answers, for each requested position and in request order, whether the bit
+        /// at that position is set (activated) within the binary representation of the value stored
+        /// in the struct instance. If a provided bit position is out of range (greater than or equal
+        /// to the bit width of `T`), it is reported as not set.
         ///
         /// # Parameters
         ///
-        /// - `bits`: A reference to a vector containing the positions of the bits to check within the value.
+        /// - `bits`: The positions of the bits to check, such as an array, a range or an iterator.
         ///
         /// # Returns
         ///
-        /// A vector of boolean values indicating whether each specified bit in the stored value is set
-        /// (`true`) or not (`false`).
+        /// A [`BitsOn`] iterator yielding one boolean per requested position: `true` if the bit is
+        /// set, `false` otherwise. The positions are read lazily, so nothing is allocated.
         ///
         /// # Example
         ///
-        /// ```compile_fail
-        /// let bits: Bits<u8> = Bits::new();
-        /// // Creates a new instance of `Bits` with an initial value of zero and size based on `u8`.
-        /// let positions_to_check = vec![2, 4, 6];
-        /// let activated_flags = bits.are_bits_on(&positions_to_check);
-        /// // Checks if bits at positions 2, 4, and 6 are set.
-        /// assert_eq!(activated_flags, vec![false, false, false, true, false, true, false, false]);
-        /// // Only the bits at positions 4 and 6 are set in the stored value.
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let bits = Bits::from_value(0b0101_1000_u8);
+        /// let activated_flags: Vec<bool> = bits.are_bits_on([2, 4, 6, 9]).collect();
+        /// // Checks if bits at positions 2, 4, 6 and 9 are set.
+        /// assert_eq!(activated_flags, vec![false, true, true, false]);
+        /// // Position 9 does not exist in a `u8`, so it is reported as not set.
         /// ```
         ///
         /// This example demonstrates how to use the `are_bits_on` method to check if specific bits are set within the stored value.
-
-        pub fn are_bits_on(&mut self, bits: &Vec<u8>) -> &Vec<bool> {
-            for &bit in bits {
-                if self.is_bit_on(bit) {
-                    self.activated_bits[bit as usize] = true;
-                }
-            }
-            &self.activated_bits
+        pub fn are_bits_on<I>(&self, bits: I) -> BitsOn<T, I::IntoIter>
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            BitsOn::new(*self, bits.into_iter())
         }
 
         /// Sets a specific bit in the value stored within the struct instance.
@@ -175,7 +450,7 @@ pub mod bit_manipulation {
         /// # Returns
         ///
         /// - `true` if the specified bit was successfully set.
-        /// - `false` if the provided bit position is out of range (greater than or equal to `self.size`).
+        /// - `false` if the provided bit position is out of range (greater than or equal to the bit width of `T`).
         /// # Example
         ///
         /// ```compile_fail
@@ -190,51 +465,56 @@ pub mod bit_manipulation {
         /// ```
         ///
         /// This example demonstrates how to use the `set_bit` method to set a specific bit within the stored value.
-
         pub fn set_bit(&mut self, bit: u8) -> bool {
-            if bit >= self.size {
+            if bit >= T::in_memory_size() {
                 return false;
             }
             self.value |= T::convert(bit);
-            self.activated_bits[bit as usize] = true;
             true
         }
 
         /// Sets multiple bits specified by their positions within the value stored in the struct instance.
         ///
-        /// This method sets each bit at the positions specified in the provided vector `bits` within
-        /// the binary representation of the value stored in the struct instance. If any specified bit
-        /// position is greater than or equal to the maximum bit position that the type `T` can have,
-        /// it will be set to false and skipped. After setting all the specified bits, it returns a reference to the
-        /// vector `activated_bits`, indicating which bits have been successfully set.
+        /// This method sets each bit at the requested positions within the binary representation of
+        /// the value stored in the struct instance. If any specified bit position is greater than or
+        /// equal to the maximum bit position that the type `T` can have, it is skipped and reported
+        /// as rejected.
         ///
         /// # Parameters
         ///
-        /// - `bits`: A reference to a vector containing the positions of the bits to set within the value.
+        /// - `bits`: The positions of the bits to set, such as an array, a slice iterator, a range or
+        ///   any other iterator of values convertible into `u8`.
         ///
         /// # Returns
         ///
-        /// A reference to a vector containing boolean values indicating whether each corresponding bit
-        /// in the stored value has been successfully set (`true`) or not (`false`).
+        /// A [`BatchResult`] listing the positions that were set by this call, the positions that
+        /// were already set, and the out of range positions that were rejected.
+        ///
         /// # Example
         ///
-        /// ```compile_fail
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
         /// let mut bits: Bits<u8> = Bits::new();
         /// // Creates a new instance of `Bits` with an initial value of zero and size based on `u8`.
-        /// let positions_to_set = vec![2, 4, 6];
-        /// let activated_flags = bits.set_bits(&positions_to_set);
+        /// bits.set_bit(4);
+        /// let result = bits.set_bits([2, 4, 6, 10]);
         /// // Sets the bits at positions 2, 4, and 6 within the stored value.
-        /// assert_eq!(activated_flags, &vec![false, false, true, false, true, false, true, false]);
-        /// // The returned vector indicates which bits were successfully set.
+        /// assert!(result.applied().iter_ones().eq([2, 6]));
+        /// assert!(result.unchanged().iter_ones().eq([4]));
+        /// assert!(result.rejected().eq([10]));
+        /// assert_eq!(bits.get_value(), 0b0101_0100);
         /// ```
         ///
         /// This example demonstrates how to use the `set_bits` method to set multiple bits within the stored value.
-
-        pub fn set_bits(&mut self, bits: &Vec<u8>) -> &Vec<bool> {
-            for &bit in bits {
-                self.set_bit(bit);
-            }
-            &self.activated_bits
+        pub fn set_bits<I>(&mut self, bits: I) -> BatchResult<T>
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            self.apply_batch(bits, |bits, bit| {
+                bits.set_bit(bit);
+            })
         }
 
         /// Clears a specific bit in the value stored within the struct instance.
@@ -270,57 +550,139 @@ pub mod bit_manipulation {
         /// ```
         ///
         /// This example demonstrates how to use the `clear_bit` method to clear a specific bit within the stored value.
-
         pub fn clear_bit(&mut self, bit: u8) -> bool {
-            if bit >= self.size {
+            if bit >= T::in_memory_size() {
                 return false;
             }
             self.value &= !(T::convert(bit));
-            self.activated_bits[bit as usize] = false;
             true
         }
         /// Clears multiple bits specified by their positions within the value stored in the struct instance.
         ///
-        /// This method clears each bit at the positions specified in the provided vector `bits` within
-        /// the binary representation of the value stored in the struct instance. If any specified bit
-        /// position is greater than or equal to the size of the value, it is considered out of range
-        /// and will be skipped. After clearing all the specified bits, it returns a reference to the
-        /// vector `activated_bits`, indicating which bits have been successfully cleared.
+        /// This method clears each bit at the requested positions within the binary representation
+        /// of the value stored in the struct instance. If any specified bit position is greater than
+        /// or equal to the size of the value, it is considered out of range, skipped and reported as
+        /// rejected.
         ///
         /// # Parameters
         ///
-        /// - `bits`: A reference to a vector containing the positions of the bits to clear within the value.
+        /// - `bits`: The positions of the bits to clear, such as an array, a slice iterator, a range
+        ///   or any other iterator of values convertible into `u8`.
         ///
         /// # Returns
         ///
-        /// A reference to a vector containing boolean values indicating whether each corresponding bit
-        /// in the stored value has been successfully cleared (`false`) or not (`true`).
+        /// A [`BatchResult`] listing the positions that were cleared by this call, the positions that
+        /// were already cleared, and the out of range positions that were rejected.
         ///
         /// # Example
         ///
-        /// ```compile_fail
-        /// let mut bits: Bits<u8> = Bits::new();
-        /// // Creates a new instance of `Bits` with an initial value of zero and size based on `u8`.
-        /// bits.set_bit(2);
-        /// bits.set_bit(4);
-        /// bits.set_bit(6);
-        /// // Sets the bits at positions 2, 4, and 6 within the stored value.
-        /// assert_eq!(bits.get_value(), 84); // binary representation: 1010100
-        /// let positions_to_clear = vec![2, 4, 6];
-        /// let cleared_flags = bits.clear_bits(&positions_to_clear);
-        /// // Clears the bits at positions 2, 4, and 6 within the stored value.
-        /// assert_eq!(cleared_flags, &vec![false, false, false, false, false, false, false, false]);
-        /// // The returned vector indicates which bits were successfully cleared.
-        /// assert_eq!(bits.get_value(), 0); // After clearing, the value becomes 0.
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let mut bits = Bits::from_value(0b0101_0100_u8);
+        /// // Creates a new instance of `Bits` with the bits at positions 2, 4 and 6 set.
+        /// let positions_to_clear = vec![2_u8, 3, 4];
+        /// let result = bits.clear_bits(positions_to_clear.iter().copied());
+        /// // Clears the bits at positions 2, 3 and 4 within the stored value.
+        /// assert!(result.applied().iter_ones().eq([2, 4]));
+        /// assert!(result.unchanged().iter_ones().eq([3]));
+        /// assert_eq!(bits.get_value(), 0b0100_0000);
         /// ```
         ///
         /// This example demonstrates how to use the `clear_bits` method to clear multiple bits within the stored value.
+        pub fn clear_bits<I>(&mut self, bits: I) -> BatchResult<T>
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            self.apply_batch(bits, |bits, bit| {
+                bits.clear_bit(bit);
+            })
+        }
 
-        pub fn clear_bits(&mut self, bits: &Vec<u8>) -> &Vec<bool> {
-            for &bit in bits {
-                self.clear_bit(bit);
+        /// Flips a specific bit in the value stored within the struct instance.
+        ///
+        /// A bit that is set is cleared, and a bit that is cleared is set. If the provided bit
+        /// position is greater than or equal to the bit width of `T`, no action is taken.
+        ///
+        /// # Parameters
+        ///
+        /// - `bit`: The position of the bit to flip within the value, ranging from 0 to `n - 1`.
+        ///
+        /// # Returns
+        ///
+        /// - `true` if the specified bit was successfully flipped.
+        /// - `false` if the provided bit position is out of range (greater than or equal to `n`).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let mut bits = Bits::from_value(0b0100_u8);
+        /// assert!(bits.toggle_bit(2));
+        /// assert!(bits.toggle_bit(0));
+        /// assert_eq!(bits.get_value(), 0b0001);
+        /// assert!(!bits.toggle_bit(8));
+        /// ```
+        pub fn toggle_bit(&mut self, bit: u8) -> bool {
+            if bit >= T::in_memory_size() {
+                return false;
             }
-            &self.activated_bits
+            self.value ^= T::convert(bit);
+            true
+        }
+
+        /// Flips multiple bits specified by their positions within the value stored in the struct
+        /// instance.
+        ///
+        /// Out of range positions are skipped. A position listed twice is flipped twice, and is
+        /// therefore left unchanged.
+        ///
+        /// # Parameters
+        ///
+        /// - `bits`: The positions of the bits to flip, such as an array, a range or an iterator.
+        ///
+        /// # Returns
+        ///
+        /// A [`BatchResult`] reporting the net change of the batch: a position flipped an odd number
+        /// of times is applied, a position flipped an even number of times is unchanged, and an out
+        /// of range position is rejected.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let mut bits = Bits::from_value(0b0011_u8);
+        /// bits.toggle_bits(1..3);
+        /// assert_eq!(bits.get_value(), 0b0101);
+        /// ```
+        pub fn toggle_bits<I>(&mut self, bits: I) -> BatchResult<T>
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            self.apply_batch(bits, |bits, bit| {
+                bits.toggle_bit(bit);
+            })
+        }
+
+        /// Flips every bit of the value stored within the struct instance.
+        ///
+        /// This is the in-place form of [`Bits::complement`].
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let mut bits = Bits::from_value(0b1111_0000_u8);
+        /// bits.invert();
+        /// assert_eq!(bits.get_value(), 0b0000_1111);
+        /// ```
+        pub fn invert(&mut self) {
+            self.value = !self.value;
         }
 
         /// Clears all bits, setting them to 0.
@@ -343,9 +705,8 @@ pub mod bit_manipulation {
         /// ```
         ///
         /// This example demonstrates how to use the `clear_all_bits` method to clear all bits within the stored value.
-
         pub fn clear_all_bits(&mut self) {
-            self.value = T::from(0);
+            self.value = T::ZERO;
         }
 
         /// Returns the current value of the unsigned integer type `T`.
@@ -369,44 +730,107 @@ pub mod bit_manipulation {
         /// ```
         ///
         /// This example demonstrates how to use the `get_value` method to retrieve the current value stored within the `Bits` struct.
-
-        pub fn get_value(&self) -> T {
+        pub const fn get_value(&self) -> T {
             self.value
         }
 
-        /// Returns a reference to the vector representing the state of all bits.
+        /// Overwrites the stored value, replacing the state of every bit at once.
+        ///
+        /// # Parameters
         ///
-        /// This method returns a reference to the vector `activated_bits`, which represents the state
-        /// of all bits within the value stored in the struct instance.
+        /// - `value`: The new value of the stored unsigned integer.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let mut bits: Bits<u8> = Bits::new();
+        /// bits.set_value(0b100);
+        /// assert!(bits.is_bit_on(2));
+        /// ```
+        pub fn set_value(&mut self, value: T) {
+            self.value = value;
+        }
+
+        /// Overwrites the stored value and returns the previous one.
+        ///
+        /// # Parameters
+        ///
+        /// - `value`: The new value of the stored unsigned integer.
+        ///
+        /// # Returns
+        ///
+        /// The value that was stored before the call.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let mut bits = Bits::from_value(3_u16);
+        /// assert_eq!(bits.replace(8), 3);
+        /// assert_eq!(bits.get_value(), 8);
+        /// ```
+        pub fn replace(&mut self, value: T) -> T {
+            core::mem::replace(&mut self.value, value)
+        }
+
+        /// Returns the stored value, clearing all bits.
         ///
         /// # Returns
         ///
-        /// A reference to a vector containing boolean values indicating the state of each bit
+        /// The value that was stored before the call.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
+        /// let mut bits = Bits::from_value(42_u32);
+        /// assert_eq!(bits.take(), 42);
+        /// assert_eq!(bits.get_value(), 0);
+        /// ```
+        pub fn take(&mut self) -> T {
+            self.replace(T::ZERO)
+        }
+
+        /// Returns a view representing the state of all bits.
+        ///
+        /// This method returns a [`BitView`] computed lazily from the value stored in the struct
+        /// instance, yielding the state of every bit from position 0 to `n - 1`. The view is taken
+        /// from a copy of the value, so it always reflects the value at the time of the call.
+        ///
+        /// # Returns
+        ///
+        /// A [`BitView`] yielding boolean values indicating the state of each bit
         /// within the stored value.
         ///
         /// # Example
         ///
-        /// ```compile_fail
+        /// ```
+        /// use bit_manipulation::bit_manipulation::Bits;
+        ///
         /// let mut bits: Bits<u8> = Bits::new();
         /// // Creates a new instance of `Bits` with an initial value of zero and size based on `u8`.
         /// bits.set_bit(2);
         /// bits.set_bit(4);
         /// bits.set_bit(6);
         /// // Sets the bits at positions 2, 4, and 6 within the stored value.
-        /// assert_eq!(bits.get_all_bits(), &vec![false, false, true, false, true, false, true, false]);
+        /// assert!(bits.get_all_bits().eq([false, false, true, false, true, false, true, false]));
         /// ```
         ///
         /// This example demonstrates how to use the `get_all_bits` method to retrieve the state of all bits within the stored value.
-
-        pub fn get_all_bits(&self) -> &Vec<bool> {
-            &self.activated_bits
+        pub fn get_all_bits(&self) -> BitView<T> {
+            BitView::new(self.value)
         }
 
         /// Sets all bits to 1, effectively setting the value to the maximum possible value of the given type `T`.
         ///
         /// This method sets all bits within the value stored in the struct instance to 1, effectively setting
         /// the value to the maximum possible value of the given type `T`. The behavior varies based on the
-        /// maximum value representable by the type `T`.
+        /// maximum value representable by the type `T`. Signed integers are handled as raw bit
+        /// patterns, so their value becomes `-1`.
         ///
         /// # Example
         ///
@@ -436,9 +860,245 @@ pub mod bit_manipulation {
         ///
         /// This example demonstrates how to use the `set_all_flags` method to set all bits to 1, effectively
         /// setting the value to the maximum possible value of the given type `T`.
-
         pub fn set_all_flags(&mut self) {
-            self.value = T::max_value();
+            self.value = T::ONES;
+        }
+
+        /// Checks if a specific bit is set (activated), rejecting out of range positions.
+        ///
+        /// This is the fallible counterpart of [`Bits::is_bit_on`]: instead of reporting an out of
+        /// range position as a cleared bit, it returns an error.
+        ///
+        /// # Parameters
+        ///
+        /// - `bit`: The position of the bit to check within the value, ranging from 0 to `n - 1`.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(true)` if the specified bit is set, `Ok(false)` if it is not.
+        /// - `Err(BitError::OutOfRange)` if the provided bit position is greater than or equal to `n`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::{BitError, Bits};
+        ///
+        /// let bits: Bits<u8> = Bits::new();
+        /// assert_eq!(bits.try_is_bit_on(2), Ok(false));
+        /// assert_eq!(bits.try_is_bit_on(8), Err(BitError::OutOfRange { bit: 8, width: 8 }));
+        /// ```
+        pub fn try_is_bit_on(&self, bit: u8) -> Result<bool, BitError> {
+            Self::check_bit(bit)?;
+            Ok(self.is_bit_on(bit))
+        }
+
+        /// Sets a specific bit, rejecting out of range positions.
+        ///
+        /// This is the fallible counterpart of [`Bits::set_bit`].
+        ///
+        /// # Parameters
+        ///
+        /// - `bit`: The position of the bit to set within the value, ranging from 0 to `n - 1`.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` if the specified bit was successfully set.
+        /// - `Err(BitError::OutOfRange)` if the provided bit position is greater than or equal to `n`,
+        ///   in which case the value is left unchanged.
+        pub fn try_set_bit(&mut self, bit: u8) -> Result<(), BitError> {
+            Self::check_bit(bit)?;
+            self.set_bit(bit);
+            Ok(())
+        }
+
+        /// Sets multiple bits, rejecting the whole batch if any position is out of range.
+        ///
+        /// This is the fallible counterpart of [`Bits::set_bits`]. Every position is validated before
+        /// the value is modified, so either all the bits are set or none of them is.
+        ///
+        /// # Parameters
+        ///
+        /// - `bits`: The positions of the bits to set, such as an array, a range or an iterator.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` if all the specified bits were successfully set.
+        /// - `Err(BitError::BatchOutOfRange)` listing every rejected position otherwise.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use bit_manipulation::bit_manipulation::{BitError, Bits};
+        ///
+        /// let mut bits: Bits<u8> = Bits::new();
+        /// let error = bits.try_set_bits([1, 9, 3, 12]).unwrap_err();
+        /// assert_eq!(error, BitError::BatchOutOfRange { bits: vec![9, 12], width: 8 });
+        /// assert_eq!(bits.get_value(), 0);
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn try_set_bits<I>(&mut self, bits: I) -> Result<(), BitError>
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            *self |= Self::check_bits(bits)?;
+            Ok(())
+        }
+
+        /// Clears a specific bit, rejecting out of range positions.
+        ///
+        /// This is the fallible counterpart of [`Bits::clear_bit`].
+        ///
+        /// # Parameters
+        ///
+        /// - `bit`: The position of the bit to clear within the value, ranging from 0 to `n - 1`.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` if the specified bit was successfully cleared.
+        /// - `Err(BitError::OutOfRange)` if the provided bit position is greater than or equal to `n`,
+        ///   in which case the value is left unchanged.
+        pub fn try_clear_bit(&mut self, bit: u8) -> Result<(), BitError> {
+            Self::check_bit(bit)?;
+            self.clear_bit(bit);
+            Ok(())
+        }
+
+        /// Clears multiple bits, rejecting the whole batch if any position is out of range.
+        ///
+        /// This is the fallible counterpart of [`Bits::clear_bits`]. Every position is validated
+        /// before the value is modified, so either all the bits are cleared or none of them is.
+        ///
+        /// # Parameters
+        ///
+        /// - `bits`: The positions of the bits to clear, such as an array, a range or an iterator.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` if all the specified bits were successfully cleared.
+        /// - `Err(BitError::BatchOutOfRange)` listing every rejected position otherwise.
+        #[cfg(feature = "alloc")]
+        pub fn try_clear_bits<I>(&mut self, bits: I) -> Result<(), BitError>
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            *self -= Self::check_bits(bits)?;
+            Ok(())
+        }
+
+        /// Checks if a specific bit is set (activated), panicking on out of range positions.
+        ///
+        /// The `strict_` methods behave like their `try_` counterparts but panic with the
+        /// [`BitError`] message instead of returning it. They are meant for tests and for code
+        /// where an out of range position is a bug.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `bit` is greater than or equal to the bit width of `T`.
+        #[track_caller]
+        pub fn strict_is_bit_on(&self, bit: u8) -> bool {
+            self.try_is_bit_on(bit)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+
+        /// Sets a specific bit, panicking on out of range positions.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `bit` is greater than or equal to the bit width of `T`.
+        #[track_caller]
+        pub fn strict_set_bit(&mut self, bit: u8) {
+            self.try_set_bit(bit)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+
+        /// Sets multiple bits, panicking if any position is out of range.
+        ///
+        /// # Panics
+        ///
+        /// Panics if any position is greater than or equal to the bit width of `T`. The panic
+        /// message lists every rejected position and the value is left unchanged.
+        #[cfg(feature = "alloc")]
+        #[track_caller]
+        pub fn strict_set_bits<I>(&mut self, bits: I)
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            self.try_set_bits(bits)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+
+        /// Clears a specific bit, panicking on out of range positions.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `bit` is greater than or equal to the bit width of `T`.
+        #[track_caller]
+        pub fn strict_clear_bit(&mut self, bit: u8) {
+            self.try_clear_bit(bit)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+
+        /// Clears multiple bits, panicking if any position is out of range.
+        ///
+        /// # Panics
+        ///
+        /// Panics if any position is greater than or equal to the bit width of `T`. The panic
+        /// message lists every rejected position and the value is left unchanged.
+        #[cfg(feature = "alloc")]
+        #[track_caller]
+        pub fn strict_clear_bits<I>(&mut self, bits: I)
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            self.try_clear_bits(bits)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+
+        fn check_bit(bit: u8) -> Result<(), BitError> {
+            if bit >= T::in_memory_size() {
+                return Err(BitError::OutOfRange {
+                    bit: bit as usize,
+                    width: T::in_memory_size() as usize,
+                });
+            }
+            Ok(())
+        }
+
+        /// Collects the requested positions, or every out of range one if there is any.
+        #[cfg(feature = "alloc")]
+        fn check_bits<I>(bits: I) -> Result<Self, BitError>
+        where
+            I: IntoIterator,
+            I::Item: Into<u8>,
+        {
+            let mut requested = Bits::new();
+            let mut rejected = alloc::vec::Vec::new();
+            for bit in bits {
+                let bit = bit.into();
+                if !requested.set_bit(bit) {
+                    rejected.push(bit as usize);
+                }
+            }
+            if !rejected.is_empty() {
+                return Err(BitError::BatchOutOfRange {
+                    bits: rejected,
+                    width: T::in_memory_size() as usize,
+                });
+            }
+            Ok(requested)
+        }
+    }
+
+    impl<T> From<T> for Bits<T>
+    where
+        T: private::Uint,
+    {
+        fn from(value: T) -> Self {
+            Bits::from_value(value)
         }
     }
 }
@@ -489,13 +1149,14 @@ mod tests {
     #[test]
     fn set_bits() {
         let mut bits: Bits<u8> = bit_manipulation::Bits::new();
-        let arr = vec![1, 3, 5, 2, 65];
-        let res = bits.set_bits(&arr);
+        let arr = [1, 3, 5, 2, 65];
+        let res = bits.set_bits(arr);
 
-        assert_eq!(
-            res,
-            &vec![false, true, true, true, false, true, false, false]
-        );
+        assert!(bits
+            .get_all_bits()
+            .eq([false, true, true, true, false, true, false, false]));
+        assert!(res.applied().iter_ones().eq([1, 2, 3, 5]));
+        assert!(res.rejected().eq([65]));
 
         bits.clear_all_bits();
 
@@ -524,24 +1185,35 @@ mod tests {
     #[test]
     fn clear_bits() {
         let mut bits: Bits<u8> = bit_manipulation::Bits::new();
-        let arr = vec![1, 3, 5, 2, 65];
-        let mut activated_bits = bits.set_bits(&arr);
+        let arr = [1, 3, 5, 2, 65];
+        bits.set_bits(arr);
 
-        assert_eq!(
-            activated_bits,
-            &vec![false, true, true, true, false, true, false, false]
-        );
+        assert_eq!(bits.get_value(), 0b0010_1110);
 
-        activated_bits = bits.clear_bits(&arr);
+        let res = bits.clear_bits(arr);
 
-        assert_eq!(
-            activated_bits,
-            &vec![false, false, false, false, false, false, false, false]
-        );
+        assert_eq!(res.applied().get_value(), 0b0010_1110);
+        assert!(res.rejected().eq([65]));
 
         assert_eq!(bits.get_value(), 0);
     }
 
+    #[test]
+    fn toggle_bits() {
+        let mut bits: Bits<u16> = Bits::new();
+
+        assert!(bits.toggle_bit(15));
+        assert!(!bits.toggle_bit(16));
+        let res = bits.toggle_bits([0, 3, 3, 20]);
+        assert_eq!(bits.get_value(), 0x8001);
+        assert_eq!(res.applied().get_value(), 0b0001);
+        assert_eq!(res.unchanged().get_value(), 0b1000);
+        assert!(res.rejected().eq([20]));
+
+        bits.invert();
+        assert_eq!(bits.get_value(), 0x7FFE);
+    }
+
     #[test]
     fn are_bits_on() {
         // Create a new bit set for u8 integers
@@ -549,20 +1221,19 @@ mod tests {
         let arr = vec![1, 3, 5, 2, 65];
 
         // Set multiple bits
-        bits.set_bits(&arr);
+        bits.set_bits(arr.iter().copied());
 
         // Check if specific bits are on
-        let activated_bits = bits.are_bits_on(&arr);
-        assert_eq!(
-            activated_bits,
-            &vec![false, true, true, true, false, true, false, false]
-        );
+        let activated_bits: Vec<bool> = bits.are_bits_on(arr.iter().copied()).collect();
+        assert_eq!(activated_bits, vec![true, true, true, true, false]);
+        assert!(bits.are_bits_on(0..4).eq([false, true, true, true]));
 
         // Clear all bits
         bits.clear_all_bits();
 
         // Check if all bits are cleared
         assert_eq!(bits.get_value(), 0);
+        assert!(bits.are_bits_on(arr).all(|on| !on));
     }
 
     #[test]
@@ -583,4 +1254,263 @@ mod tests {
         bits.set_all_flags();
         assert_eq!(u128::MAX, bits.get_value());
     }
+    #[test]
+    fn views_follow_value() {
+        let mut bits: Bits<u8> = Bits::new();
+        bits.set_all_flags();
+        assert!(bits.get_all_bits().all(|on| on));
+
+        bits.clear_all_bits();
+        assert!(bits.get_all_bits().all(|on| !on));
+
+        bits.set_bit(7);
+        let view = bits.get_all_bits();
+        assert_eq!(view.len(), 8);
+        assert_eq!(view.rev().position(|on| on), Some(0));
+
+        assert_eq!(std::mem::size_of::<Bits<u8>>(), std::mem::size_of::<u8>());
+        assert_eq!(
+            std::mem::size_of::<Bits<u128>>(),
+            std::mem::size_of::<u128>()
+        );
+    }
+    #[test]
+    fn try_operations() {
+        let mut bits: Bits<u16> = Bits::new();
+
+        assert_eq!(bits.try_set_bit(15), Ok(()));
+        assert_eq!(bits.try_is_bit_on(15), Ok(true));
+        assert_eq!(
+            bits.try_set_bit(16),
+            Err(bit_manipulation::BitError::OutOfRange { bit: 16, width: 16 })
+        );
+        assert_eq!(bits.try_clear_bit(15), Ok(()));
+        assert!(bits.try_clear_bit(200).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_batch_operations() {
+        let mut bits: Bits<u16> = Bits::new();
+
+        assert_eq!(
+            bits.try_set_bits([0, 16, 4, 31]),
+            Err(bit_manipulation::BitError::BatchOutOfRange {
+                bits: vec![16, 31],
+                width: 16
+            })
+        );
+        assert_eq!(bits.get_value(), 0);
+
+        assert_eq!(bits.try_set_bits([0, 4]), Ok(()));
+        assert!(bits.try_clear_bits(vec![0_u8, 4, 20]).is_err());
+        assert_eq!(bits.get_value(), 0b1_0001);
+        assert_eq!(bits.try_clear_bits(0..5), Ok(()));
+        assert_eq!(bits.get_value(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit 8 is out of range for a width of 8 bits")]
+    fn strict_set_bit_panics() {
+        let mut bits: Bits<u8> = Bits::new();
+        bits.strict_set_bit(7);
+        bits.strict_set_bit(8);
+    }
+    #[test]
+    fn from_value() {
+        let mut bits = Bits::from_value(0b1000_0001_u8);
+        assert!(bits.is_bit_on(0));
+        assert!(bits.is_bit_on(7));
+        assert!(bits
+            .get_all_bits()
+            .eq([true, false, false, false, false, false, false, true]));
+
+        bits.set_value(0b10);
+        assert!(bits
+            .get_all_bits()
+            .eq([false, true, false, false, false, false, false, false]));
+
+        assert_eq!(bits.replace(u8::MAX), 0b10);
+        assert!(bits.get_all_bits().all(|on| on));
+
+        assert_eq!(bits.take(), u8::MAX);
+        assert!(bits.get_all_bits().all(|on| !on));
+
+        let bits: Bits<u64> = (1_u64 << 40).into();
+        assert!(bits.is_bit_on(40));
+        let value: u64 = bits.into();
+        assert_eq!(value, 1 << 40);
+        assert_eq!(u128::from(Bits::from(7_u128)), 7);
+    }
+    #[test]
+    fn std_traits() {
+        use std::collections::{BTreeSet, HashMap};
+
+        let read = Bits::from_value(0b001_u8);
+        let write = Bits::from_value(0b010_u8);
+
+        let mut owners = HashMap::new();
+        owners.insert(read | write, "admin");
+        assert_eq!(owners.get(&Bits::from_value(0b011)), Some(&"admin"));
+
+        let mut sorted = vec![write | read, write, read];
+        sorted.sort();
+        assert_eq!(sorted, vec![read, write, read | write]);
+        assert!(read < write);
+
+        let ordered: BTreeSet<Bits<u8>> = sorted.into_iter().collect();
+        assert_eq!(ordered.len(), 3);
+    }
+
+    #[test]
+    fn signed_and_pointer_sized() {
+        let mut bits: Bits<i8> = Bits::new();
+        bits.set_bit(7);
+        assert!(bits.is_bit_on(7));
+        assert_eq!(bits.get_value(), i8::MIN);
+        assert!(bits.iter_ones().eq([7]));
+        assert_eq!(bits.get_field(4..8), Ok(0b1000));
+
+        bits.set_field(0..8, -1).unwrap();
+        assert_eq!(bits.to_string(), "11111111");
+        assert_eq!(
+            bits.set_field(0..4, -1),
+            Err(bit_manipulation::BitError::FieldOverflow { field_width: 4 })
+        );
+
+        let mut bits: Bits<i128> = Bits::new();
+        bits.set_all_flags();
+        assert_eq!(bits.get_value(), -1);
+        assert!(bits.clear_bit(127));
+        assert_eq!(bits.get_value(), i128::MAX);
+
+        let mut bits: Bits<usize> = Bits::new();
+        assert!(bits.set_bit(usize::BITS as u8 - 1));
+        assert!(!bits.set_bit(usize::BITS as u8));
+        assert_eq!(bits.get_value(), 1 << (usize::BITS - 1));
+    }
+
+    #[test]
+    fn non_zero_conversions() {
+        use std::num::{NonZeroI16, NonZeroU32};
+
+        let bits = Bits::from(NonZeroU32::new(0b101).unwrap());
+        assert_eq!(bits.get_value(), 0b101);
+        assert_eq!(NonZeroU32::try_from(bits).unwrap().get(), 0b101);
+        assert_eq!(NonZeroI16::try_from(Bits::<i16>::new()), Err(Bits::new()));
+    }
+
+    #[test]
+    fn const_construction() {
+        const EMPTY: Bits<u8> = Bits::new();
+        static SIGNED: Bits<i8> = Bits::from_value(1_i8).with_bit(7);
+        const _: () = assert!(SIGNED.has_bit(7));
+
+        assert_eq!(EMPTY.get_value(), 0);
+        assert_eq!(SIGNED.get_value(), -127);
+        assert!(!SIGNED.has_bit(8));
+        assert_eq!(Bits::<u128>::new().with_bit(127).get_value(), 1 << 127);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit position is out of range")]
+    fn const_construction_out_of_range() {
+        let _ = Bits::<u32>::new().with_bit(32);
+    }
+
+    #[test]
+    fn custom_storage() {
+        use std::ops::{
+            BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr,
+        };
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Word(u16);
+
+        macro_rules! word_operator {
+            ($($trait:ident $method:ident $assign:ident $assign_method:ident),*) => {$(
+                impl $trait for Word {
+                    type Output = Word;
+
+                    fn $method(self, rhs: Word) -> Word {
+                        Word(self.0.$method(rhs.0))
+                    }
+                }
+
+                impl $assign for Word {
+                    fn $assign_method(&mut self, rhs: Word) {
+                        self.0.$assign_method(rhs.0);
+                    }
+                }
+            )*};
+        }
+
+        word_operator!(
+            BitAnd bitand BitAndAssign bitand_assign,
+            BitOr bitor BitOrAssign bitor_assign,
+            BitXor bitxor BitXorAssign bitxor_assign
+        );
+
+        impl Not for Word {
+            type Output = Word;
+
+            fn not(self) -> Word {
+                Word(!self.0)
+            }
+        }
+
+        impl Shl<u8> for Word {
+            type Output = Word;
+
+            fn shl(self, rhs: u8) -> Word {
+                Word(self.0 << rhs)
+            }
+        }
+
+        impl Shr<u8> for Word {
+            type Output = Word;
+
+            fn shr(self, rhs: u8) -> Word {
+                Word(self.0 >> rhs)
+            }
+        }
+
+        impl crate::BitStorage for Word {
+            const BITS: u8 = 16;
+            const ZERO: Self = Word(0);
+            const ONES: Self = Word(u16::MAX);
+
+            fn convert(bit: u8) -> Self {
+                Word(1 << bit)
+            }
+            fn count_ones(self) -> u32 {
+                self.0.count_ones()
+            }
+            fn leading_zeros(self) -> u32 {
+                self.0.leading_zeros()
+            }
+            fn trailing_zeros(self) -> u32 {
+                self.0.trailing_zeros()
+            }
+
+            type Bytes = [u8; 2];
+
+            fn to_le_bytes(self) -> [u8; 2] {
+                self.0.to_le_bytes()
+            }
+            fn from_le_bytes(bytes: [u8; 2]) -> Self {
+                Word(u16::from_le_bytes(bytes))
+            }
+        }
+
+        let mut bits: Bits<Word> = Bits::new();
+        bits.set_bits([1, 15, 16]);
+        assert!(bits.iter_ones().eq([1, 15]));
+        assert_eq!(bits.get_value(), Word(0x8002));
+        assert_eq!(bits.count_range(..8), Ok(1));
+        assert_eq!(bits.rotate_left(17).get_value(), Word(0x0005));
+        assert_eq!(bits.rotate_right(2).get_value(), Word(0xA000));
+        assert_eq!(bits.reverse_bits().get_value(), Word(0x4001));
+        assert_eq!(bits.swap_bytes().get_value(), Word(0x0280));
+    }
 }
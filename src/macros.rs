@@ -0,0 +1,383 @@
+use crate::private;
+
+/// Selects a backing integer type from its width class, see [`narrowest_width`].
+pub struct Width<const CLASS: u8>;
+
+/// Maps a [`Width`] class to the unsigned integer type used to store it.
+#[diagnostic::on_unimplemented(
+    message = "bit positions must be lower than 128",
+    label = "no unsigned integer is wide enough for these positions"
+)]
+pub trait Backing {
+    type Type: private::Uint;
+}
+
+impl Backing for Width<0> {
+    type Type = u8;
+}
+
+impl Backing for Width<1> {
+    type Type = u16;
+}
+
+impl Backing for Width<2> {
+    type Type = u32;
+}
+
+impl Backing for Width<3> {
+    type Type = u64;
+}
+
+impl Backing for Width<4> {
+    type Type = u128;
+}
+
+/// Returns the width class of the narrowest unsigned integer able to hold every position:
+/// 0 for `u8` up to 4 for `u128`, and 5 when a position does not fit in a `u128`.
+pub const fn narrowest_width(positions: &[u8]) -> u8 {
+    let mut highest = 0;
+    let mut i = 0;
+    while i < positions.len() {
+        if positions[i] > highest {
+            highest = positions[i];
+        }
+        i += 1;
+    }
+    match highest {
+        0..=7 => 0,
+        8..=15 => 1,
+        16..=31 => 2,
+        32..=63 => 3,
+        64..=127 => 4,
+        _ => 5,
+    }
+}
+
+/// Returns the bit width of `T`, see [`Uint::in_memory_size`](private::Uint::in_memory_size).
+pub const fn bit_width<T: private::Uint>() -> u8 {
+    T::BITS
+}
+
+/// Returns `true` if the same position appears more than once.
+pub const fn has_duplicates(positions: &[u8]) -> bool {
+    let mut i = 0;
+    while i < positions.len() {
+        let mut j = i + 1;
+        while j < positions.len() {
+            if positions[i] == positions[j] {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Declares a set of named options stored in a [`Bits`](crate::bit_manipulation::Bits).
+///
+/// Each option is given a name and a bit position. The macro generates a `Copy` struct wrapping
+/// a `Bits<T>`, where `T` is the narrowest unsigned integer able to hold the highest position,
+/// and one associated constant per option. The options are combined with `|`, `&` and `-`, and
+/// changed or checked with the generated `set`, `clear` and `contains` methods, which delegate to
+/// the underlying `Bits<T>`. Option sets are written and parsed as flag expressions such as
+/// `READ | WRITE`, see [`FlagTable`](crate::bit_manipulation::FlagTable), when the `alloc`
+/// feature is enabled. With the `serde` feature, they are serialized as a list of option names.
+///
+/// Positions must be unique and lower than 128, otherwise the declaration does not compile.
+///
+/// # Example
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// use bit_manipulation::bit_options;
+///
+/// bit_options! {
+///     /// Permissions of a file.
+///     pub struct Permissions {
+///         const READ = 0;
+///         const WRITE = 1;
+///         const EXEC = 2;
+///     }
+/// }
+///
+/// let mut permissions = Permissions::READ | Permissions::WRITE;
+/// assert!(permissions.contains(Permissions::READ));
+///
+/// permissions.clear(Permissions::WRITE);
+/// permissions.set(Permissions::EXEC);
+/// assert_eq!(permissions.bits().get_value(), 0b101_u8);
+/// assert_eq!(Permissions::all().bits().get_value(), 0b111);
+///
+/// assert_eq!(permissions.to_string(), "READ | EXEC");
+/// assert_eq!("!WRITE".parse(), Ok(permissions));
+/// ```
+///
+/// Duplicate or out of range positions are rejected at compile time:
+///
+/// ```compile_fail
+/// use bit_manipulation::bit_options;
+///
+/// bit_options! {
+///     pub struct Permissions {
+///         const READ = 0;
+///         const WRITE = 0;
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use bit_manipulation::bit_options;
+///
+/// bit_options! {
+///     pub struct Permissions {
+///         const READ = 128;
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! bit_options {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$option_meta:meta])*
+                const $option:ident = $position:literal;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name(
+            $crate::bit_manipulation::Bits<
+                <$crate::__private::Width<
+                    { $crate::__private::narrowest_width(&[$($position),*]) },
+                > as $crate::__private::Backing>::Type,
+            >,
+        );
+
+        const _: () = {
+            assert!(
+                $crate::__private::narrowest_width(&[$($position),*]) < 5,
+                concat!("bit_options!: the positions of `", stringify!($name), "` must be lower than 128"),
+            );
+            assert!(
+                !$crate::__private::has_duplicates(&[$($position),*]),
+                concat!("bit_options!: the positions of `", stringify!($name), "` must be unique"),
+            );
+        };
+
+        #[allow(dead_code)]
+        impl $name {
+            $(
+                $(#[$option_meta])*
+                pub const $option: Self = Self($crate::bit_manipulation::Bits::from_value(1 << $position));
+            )*
+
+            /// Every declared option along with its name, in declaration order.
+            pub const OPTIONS: &'static [(&'static str, Self)] = &[$((stringify!($option), Self::$option)),*];
+
+            /// The name table used to parse and format the options with `FromStr` and `Display`.
+            pub const NAMES: $crate::bit_manipulation::FlagTable<
+                'static,
+                <$crate::__private::Width<
+                    { $crate::__private::narrowest_width(&[$($position),*]) },
+                > as $crate::__private::Backing>::Type,
+            > = $crate::bit_manipulation::FlagTable::new(&[$((stringify!($option), Self::$option.0)),*]);
+
+            /// Returns a set with no option enabled.
+            pub const fn empty() -> Self {
+                Self($crate::bit_manipulation::Bits::from_value(0))
+            }
+
+            /// Returns a set with every declared option enabled.
+            pub const fn all() -> Self {
+                Self($crate::bit_manipulation::Bits::from_value(0 $(| 1 << $position)*))
+            }
+
+            /// Returns the underlying bits.
+            pub const fn bits(&self) -> $crate::bit_manipulation::Bits<
+                <$crate::__private::Width<
+                    { $crate::__private::narrowest_width(&[$($position),*]) },
+                > as $crate::__private::Backing>::Type,
+            > {
+                self.0
+            }
+
+            /// Returns `true` if no option is enabled.
+            pub fn is_empty(&self) -> bool {
+                self.0.iter_ones().next().is_none()
+            }
+
+            /// Enables every option of `options`.
+            pub fn set(&mut self, options: Self) {
+                self.0 |= options.0;
+            }
+
+            /// Disables every option of `options`.
+            pub fn clear(&mut self, options: Self) {
+                self.0 -= options.0;
+            }
+
+            /// Returns `true` if every option of `options` is enabled.
+            pub fn contains(&self, options: Self) -> bool {
+                options.0.is_subset(&self.0)
+            }
+        }
+
+        $crate::__bit_options_text!($name);
+        $crate::__bit_options_serde!($name);
+
+        impl ::core::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl ::core::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl ::core::ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+    };
+}
+
+/// Implements `Display` and `FromStr` for a [`bit_options!`] struct, through its name table.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bit_options_text {
+    ($name:ident) => {
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&Self::NAMES.format(self.0))
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::bit_manipulation::BitError;
+
+            fn from_str(text: &str) -> ::core::result::Result<Self, Self::Err> {
+                Self::NAMES.parse(text).map(Self)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bit_options_text {
+    ($name:ident) => {};
+}
+
+/// Implements `Serialize` and `Deserialize` for a [`bit_options!`] struct, as a list of option
+/// names, see [`FlagTable::serialize_names`](crate::bit_manipulation::FlagTable::serialize_names).
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bit_options_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                Self::NAMES.serialize_names(&self.0, serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                Self::NAMES.deserialize_names(deserializer).map(Self)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bit_options_serde {
+    ($name:ident) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    bit_options! {
+        struct Small {
+            const A = 0;
+            const B = 7;
+        }
+    }
+
+    bit_options! {
+        struct Wide {
+            const LOW = 3;
+            /// The highest option.
+            const HIGH = 100;
+        }
+    }
+
+    #[test]
+    fn narrowest_backing_type() {
+        assert_eq!(std::mem::size_of::<Small>(), 1);
+        assert_eq!(std::mem::size_of::<Wide>(), 16);
+        assert_eq!(narrowest_width(&[]), 0);
+        assert_eq!(narrowest_width(&[8]), 1);
+        assert_eq!(narrowest_width(&[3, 63]), 3);
+        assert_eq!(narrowest_width(&[128]), 5);
+        assert!(has_duplicates(&[1, 2, 1]));
+        assert!(!has_duplicates(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn set_clear_contains() {
+        let mut options = Wide::empty();
+        assert!(options.is_empty());
+
+        options.set(Wide::HIGH);
+        assert!(options.contains(Wide::HIGH));
+        assert!(!options.contains(Wide::LOW | Wide::HIGH));
+        assert!(options.bits().is_bit_on(100));
+
+        options.set(Wide::LOW);
+        assert_eq!(options, Wide::all());
+        assert_eq!(options & Wide::LOW, Wide::LOW);
+        assert_eq!(options - Wide::LOW, Wide::HIGH);
+
+        options.clear(Wide::all());
+        assert!(options.is_empty());
+
+        assert_eq!(Small::OPTIONS[1].0, "B");
+        assert_eq!(Small::OPTIONS[1].1, Small::B);
+        assert_eq!(Small::all().bits().get_value(), 0b1000_0001);
+        assert!(Small::A.bits().is_bit_on(0));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_and_format() {
+        assert_eq!((Wide::LOW | Wide::HIGH).to_string(), "LOW | HIGH");
+        assert_eq!(Wide::empty().to_string(), "");
+        assert_eq!("HIGH & (LOW | HIGH)".parse(), Ok(Wide::HIGH));
+        assert!("LOW | MID".parse::<Wide>().is_err());
+    }
+}